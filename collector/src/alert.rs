@@ -0,0 +1,571 @@
+use crate::data::{Metric, Sample};
+use crate::export::escape_json;
+use chrono::{DateTime, SecondsFormat, TimeDelta, Utc};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs::OpenOptions;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// Alarmların önem seviyesi. Lesson_03'teki CPU seviyeleri ile aynıdır.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Normal,
+    Warm,
+    Burning,
+    Alarm,
+}
+
+impl Level {
+    pub fn to_string(self) -> &'static str {
+        match self {
+            Level::Normal => "NORMAL",
+            Level::Warm => "WARM",
+            Level::Burning => "BURNING",
+            Level::Alarm => "ALARM",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Above,
+    Below,
+}
+
+/// Bir metrik değeri için alarm kuralı.
+///
+/// Kural `METRIC[:LABEL]>THRESHOLD[,for=SECONDS][,hysteresis=N][,level=LEVEL]` şeklinde
+/// yazılır. Eşik altı için `<` kullanılır. Örnekler:
+///
+/// `cpu>90,for=10,hysteresis=5,level=alarm`, `mem:free<500`, `disk:/>85,level=warm`
+///
+/// Etiket verilmezse kural metriğin her değeri için ayrı ayrı işletilir (örneğin her çekirdek).
+/// Değer eşiği `for` saniye boyunca aşarsa alarm verilir. Alarm, değer eşiğin `hysteresis`
+/// kadar gerisine dönene kadar devam eder.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertRule {
+    pub text: String,
+    pub metric: Metric,
+    pub label: Option<String>,
+    pub comparison: Comparison,
+    pub threshold: f64,
+    pub duration: Duration,
+    pub hysteresis: f64,
+    pub level: Level,
+}
+
+impl AlertRule {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut parts = text.split(',');
+        let condition = parts.next().unwrap_or_default();
+        let (target, threshold, comparison) = match condition.split_once('>') {
+            Some((target, threshold)) => (target, threshold, Comparison::Above),
+            None => match condition.split_once('<') {
+                Some((target, threshold)) => (target, threshold, Comparison::Below),
+                None => return Err("condition needs > or <".to_string()),
+            },
+        };
+        let (key, label) = match target.split_once(':') {
+            Some((key, label)) => (key, Some(label.to_string())),
+            None => (target, None),
+        };
+        let metric = Metric::ALL
+            .iter()
+            .find(|metric| metric.key() == key.trim().to_lowercase())
+            .cloned()
+            .ok_or_else(|| format!("unknown metric '{}'", key))?;
+        let mut rule = AlertRule {
+            text: text.to_string(),
+            metric,
+            label,
+            comparison,
+            threshold: parse_number("threshold", threshold)?,
+            duration: Duration::ZERO,
+            hysteresis: 0.0,
+            level: Level::Alarm,
+        };
+
+        for part in parts {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("'{}' should be name=value", part))?;
+            match name.trim() {
+                "for" => rule.duration = Duration::from_secs_f64(parse_number(name, value)?),
+                "hysteresis" => rule.hysteresis = parse_number(name, value)?,
+                "level" => {
+                    rule.level = match value.trim().to_lowercase().as_str() {
+                        "warm" => Level::Warm,
+                        "burning" => Level::Burning,
+                        "alarm" => Level::Alarm,
+                        _ => {
+                            return Err(format!("level '{}' is not warm, burning or alarm", value))
+                        }
+                    }
+                }
+                _ => return Err(format!("unknown setting '{}'", name)),
+            }
+        }
+        Ok(rule)
+    }
+
+    fn is_breached(&self, value: f64) -> bool {
+        match self.comparison {
+            Comparison::Above => value > self.threshold,
+            Comparison::Below => value < self.threshold,
+        }
+    }
+
+    fn is_cleared(&self, value: f64) -> bool {
+        match self.comparison {
+            Comparison::Above => value < self.threshold - self.hysteresis,
+            Comparison::Below => value > self.threshold + self.hysteresis,
+        }
+    }
+}
+
+// Negatif değerlere izin verilmez
+fn parse_number(name: &str, value: &str) -> Result<f64, String> {
+    match value.trim().parse::<f64>() {
+        Ok(number) if number >= 0.0 && number.is_finite() => Ok(number),
+        _ => Err(format!("{} '{}' is not a positive number", name, value)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlertState {
+    Firing,
+    Resolved,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertEvent {
+    pub timestamp: DateTime<Utc>,
+    pub rule: String,
+    pub label: String,
+    pub state: AlertState,
+    /// Alarm sona erdiğinde `Normal` olur
+    pub level: Level,
+    pub value: f64,
+}
+
+impl AlertEvent {
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"timestamp\": \"{}\", \"rule\": \"{}\", \"label\": \"{}\", \"state\": \"{}\", \"level\": \"{}\", \"value\": {:.2}}}",
+            self.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+            escape_json(&self.rule),
+            escape_json(&self.label),
+            match self.state {
+                AlertState::Firing => "firing",
+                AlertState::Resolved => "resolved",
+            },
+            self.level.to_string(),
+            self.value
+        )
+    }
+}
+
+impl Display for AlertEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let state = match self.state {
+            AlertState::Firing => "fired",
+            AlertState::Resolved => "resolved",
+        };
+        write!(
+            f,
+            "{} {:<7} {} [{}] {} at {:.2}",
+            self.timestamp.to_rfc3339_opts(SecondsFormat::Secs, true),
+            self.level.to_string(),
+            self.rule,
+            self.label,
+            state,
+            self.value
+        )
+    }
+}
+
+/// Kural ve etiket ikilisi için alarm geçmişi.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertSummary {
+    pub rule: String,
+    pub label: String,
+    pub fired: u32,
+    pub active: bool,
+    pub firing_time: TimeDelta,
+    /// Tüm çalışma boyunca alarm sürerken görülen en kötü değer. Alarm tekrar verildiğinde
+    /// sıfırlanmaz, `fired` ve `firing_time` gibi tüm alarmları kapsar.
+    pub peak: f64,
+}
+
+#[derive(Default)]
+struct Tracker {
+    breached_since: Option<DateTime<Utc>>,
+    fired_at: Option<DateTime<Utc>>,
+    fired: u32,
+    firing_time: TimeDelta,
+    peak: Option<f64>,
+    last_seen: Option<DateTime<Utc>>,
+}
+
+/// Ölçümleri kurallara göre değerlendirerek alarm olayları üretir.
+pub struct AlertMonitor {
+    rules: Vec<AlertRule>,
+    trackers: BTreeMap<(usize, String), Tracker>,
+}
+
+impl AlertMonitor {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        AlertMonitor {
+            rules,
+            trackers: BTreeMap::new(),
+        }
+    }
+
+    pub fn evaluate(&mut self, sample: &Sample) -> Vec<AlertEvent> {
+        let mut events = Vec::new();
+        for (index, rule) in self.rules.iter().enumerate() {
+            if rule.metric != sample.metric {
+                continue;
+            }
+            let values = sample.values.iter().filter(|value| {
+                rule.label
+                    .as_ref()
+                    .is_none_or(|label| *label == value.label)
+            });
+            for value in values {
+                let tracker = self
+                    .trackers
+                    .entry((index, value.label.clone()))
+                    .or_default();
+                let now = sample.timestamp;
+                tracker.last_seen = Some(now);
+                let event = |state, level| AlertEvent {
+                    timestamp: now,
+                    rule: rule.text.clone(),
+                    label: value.label.clone(),
+                    state,
+                    level,
+                    value: value.value,
+                };
+
+                match tracker.fired_at {
+                    None if rule.is_breached(value.value) => {
+                        let since = *tracker.breached_since.get_or_insert(now);
+                        if (now - since).to_std().unwrap_or_default() >= rule.duration {
+                            tracker.fired_at = Some(now);
+                            tracker.fired += 1;
+                            events.push(event(AlertState::Firing, rule.level));
+                        }
+                    }
+                    None => tracker.breached_since = None,
+                    Some(fired_at) if rule.is_cleared(value.value) => {
+                        tracker.firing_time += now - fired_at;
+                        tracker.fired_at = None;
+                        tracker.breached_since = None;
+                        events.push(event(AlertState::Resolved, Level::Normal));
+                    }
+                    Some(_) => {}
+                }
+                if tracker.fired_at.is_some() {
+                    let peak = tracker.peak.get_or_insert(value.value);
+                    *peak = match rule.comparison {
+                        Comparison::Above => peak.max(value.value),
+                        Comparison::Below => peak.min(value.value),
+                    };
+                }
+            }
+        }
+        events
+    }
+
+    /// Alarm vermiş kurallar için özet. Devam eden alarmların süresi son ölçüme kadar sayılır.
+    pub fn summary(&self) -> Vec<AlertSummary> {
+        self.trackers
+            .iter()
+            .filter(|(_, tracker)| tracker.fired > 0)
+            .map(|((index, label), tracker)| {
+                let active_time = match (tracker.fired_at, tracker.last_seen) {
+                    (Some(fired_at), Some(last_seen)) => last_seen - fired_at,
+                    _ => TimeDelta::zero(),
+                };
+                AlertSummary {
+                    rule: self.rules[*index].text.clone(),
+                    label: label.clone(),
+                    fired: tracker.fired,
+                    active: tracker.fired_at.is_some(),
+                    firing_time: tracker.firing_time + active_time,
+                    peak: tracker.peak.unwrap_or_default(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Alarm olaylarının gönderileceği yer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlertSink {
+    Stderr,
+    /// Olaylar satır satır dosyanın sonuna eklenir
+    File(String),
+    /// Olaylar JSON olarak yerel bir HTTP adresine POST edilir. Sadece `http://` desteklenir.
+    Webhook(String),
+}
+
+impl AlertSink {
+    /// `stderr`, `file:PATH` veya `webhook:http://host[:port]/path`. Port verilmezse 80 kullanılır.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        if value == "stderr" {
+            return Ok(AlertSink::Stderr);
+        }
+        if let Some(path) = value.strip_prefix("file:").filter(|path| !path.is_empty()) {
+            return Ok(AlertSink::File(path.to_string()));
+        }
+        if let Some(url) = value.strip_prefix("webhook:") {
+            split_url(url)?;
+            return Ok(AlertSink::Webhook(url.to_string()));
+        }
+        Err("expected stderr, file:PATH or webhook:http://host:port/path".to_string())
+    }
+
+    pub fn emit(&self, event: &AlertEvent) -> io::Result<()> {
+        match self {
+            AlertSink::Stderr => {
+                eprintln!("{}", event);
+                Ok(())
+            }
+            AlertSink::File(path) => {
+                let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+                writeln!(file, "{}", event)
+            }
+            AlertSink::Webhook(url) => post(url, &event.to_json()),
+        }
+    }
+}
+
+// "http://host:port/path" adresini sunucu, port ve "/path" olarak ayırır. Port verilmezse 80
+// kullanılır
+fn split_url(url: &str) -> Result<(&str, u16, &str), String> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| format!("webhook '{}' should start with http://", url))?;
+    let (authority, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        // IPv6 adresleri köşeli parantez içinde yazılır
+        Some((host, port)) if !port.ends_with(']') => {
+            let port = port
+                .parse::<u16>()
+                .map_err(|_| format!("webhook '{}' has an invalid port '{}'", url, port))?;
+            (host, port)
+        }
+        _ => (authority, 80),
+    };
+    if host.is_empty() {
+        return Err(format!("webhook '{}' has no host", url));
+    }
+    Ok((host, port, path))
+}
+
+fn post(url: &str, body: &str) -> io::Result<()> {
+    let (host, port, path) = split_url(url).map_err(io::Error::other)?;
+    let address = (host.trim_start_matches('[').trim_end_matches(']'), port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::other(format!("{} could not be resolved", host)))?;
+    let mut stream = TcpStream::connect_timeout(&address, Duration::from_secs(2))?;
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    write!(
+        stream,
+        "POST {} HTTP/1.1\r\nHost: {}:{}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        path,
+        host,
+        port,
+        body.len(),
+        body
+    )?;
+    let mut status_line = String::new();
+    BufReader::new(stream).read_line(&mut status_line)?;
+    match status_line.split_whitespace().nth(1) {
+        Some(status) if status.starts_with('2') => Ok(()),
+        _ => Err(io::Error::other(format!(
+            "Webhook responded with '{}'",
+            status_line.trim()
+        ))),
+    }
+}
+
+pub fn print_summary(summary: &[AlertSummary]) {
+    println!("Alert summary :");
+    if summary.is_empty() {
+        println!("    No alerts");
+        return;
+    }
+    for alert in summary {
+        println!(
+            "    {} [{}] fired {} times, {:.0} secs in total, peak {:.2}{}",
+            alert.rule,
+            alert.label,
+            alert.fired,
+            alert.firing_time.as_seconds_f64(),
+            alert.peak,
+            if alert.active { ", still active" } else { "" }
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Value;
+    use chrono::TimeZone;
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn sample(second: u32, metric: Metric, values: &[(&str, f64)]) -> Sample {
+        Sample {
+            timestamp: Utc.with_ymd_and_hms(2025, 3, 1, 10, 0, second).unwrap(),
+            metric,
+            unit: "%",
+            values: values
+                .iter()
+                .map(|(label, value)| Value::new(*label, *value))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn should_parse_alert_rules_test() {
+        let rule = AlertRule::parse("mem:free<500,for=10,hysteresis=50,level=warm").unwrap();
+        assert_eq!(rule.metric, Metric::Memory);
+        assert_eq!(rule.label.as_deref(), Some("free"));
+        assert_eq!(rule.comparison, Comparison::Below);
+        assert_eq!(rule.threshold, 500.0);
+        assert_eq!(rule.duration, Duration::from_secs(10));
+        assert_eq!(rule.hysteresis, 50.0);
+        assert_eq!(rule.level, Level::Warm);
+
+        assert_eq!(
+            AlertRule::parse("gpu>5").unwrap_err(),
+            "unknown metric 'gpu'"
+        );
+        assert_eq!(
+            AlertRule::parse("cpu=5").unwrap_err(),
+            "condition needs > or <"
+        );
+        assert_eq!(
+            AlertRule::parse("cpu>90,for=soon").unwrap_err(),
+            "for 'soon' is not a positive number"
+        );
+        assert!(AlertSink::parse("webhook:https://host/").is_err());
+        assert_eq!(
+            AlertSink::parse("file:alerts.log"),
+            Ok(AlertSink::File("alerts.log".to_string()))
+        );
+        assert_eq!(
+            split_url("http://localhost/alerts"),
+            Ok(("localhost", 80, "/alerts"))
+        );
+        assert_eq!(
+            split_url("http://127.0.0.1:9000"),
+            Ok(("127.0.0.1", 9000, "/"))
+        );
+        assert_eq!(split_url("http://[::1]/a"), Ok(("[::1]", 80, "/a")));
+        assert_eq!(split_url("http://[::1]:81/a"), Ok(("[::1]", 81, "/a")));
+        assert!(AlertSink::parse("webhook:http://localhost/alerts").is_ok());
+        assert!(AlertSink::parse("webhook:http://localhost:x/alerts").is_err());
+    }
+
+    #[test]
+    fn should_fire_after_duration_and_resolve_with_hysteresis_test() {
+        let rule = AlertRule::parse("cpu>90,for=2,hysteresis=10").unwrap();
+        let mut monitor = AlertMonitor::new(vec![rule]);
+        let mut events = Vec::new();
+        for (second, usage) in [(0, 95.0), (1, 80.0), (2, 95.0), (3, 97.0), (4, 99.0)] {
+            events.extend(monitor.evaluate(&sample(second, Metric::Cpu, &[("0", usage)])));
+        }
+        // Eşiğin altında ama hysteresis içinde kalan değer alarmı bitirmez. İkinci alarmın
+        // değerleri ilkinden düşüktür
+        for (second, usage) in [
+            (5, 85.0),
+            (6, 79.0),
+            (7, 95.0),
+            (8, 95.0),
+            (9, 93.0),
+            (10, 70.0),
+        ] {
+            events.extend(monitor.evaluate(&sample(second, Metric::Cpu, &[("0", usage)])));
+        }
+        assert!(monitor
+            .evaluate(&sample(8, Metric::Memory, &[("0", 100.0)]))
+            .is_empty());
+
+        let states: Vec<_> = events
+            .iter()
+            .map(|event| (event.timestamp.timestamp() % 60, event.state, event.level))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                (4, AlertState::Firing, Level::Alarm),
+                (6, AlertState::Resolved, Level::Normal),
+                (9, AlertState::Firing, Level::Alarm),
+                (10, AlertState::Resolved, Level::Normal),
+            ]
+        );
+
+        let summary = monitor.summary();
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].fired, 2);
+        assert_eq!(summary[0].firing_time, TimeDelta::seconds(3));
+        assert_eq!(summary[0].peak, 99.0);
+        assert!(!summary[0].active);
+    }
+
+    #[test]
+    fn should_post_events_to_webhook_test() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/alerts", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            while reader.read_line(&mut request).unwrap() > 2 {}
+            let length = request
+                .lines()
+                .find_map(|line| line.strip_prefix("Content-Length: "))
+                .unwrap()
+                .parse::<usize>()
+                .unwrap();
+            let mut body = vec![0; length];
+            std::io::Read::read_exact(&mut reader, &mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+            request
+        });
+
+        let event = AlertEvent {
+            timestamp: Utc.with_ymd_and_hms(2025, 3, 1, 10, 0, 0).unwrap(),
+            rule: "cpu>90".to_string(),
+            label: "0".to_string(),
+            state: AlertState::Firing,
+            level: Level::Alarm,
+            value: 95.0,
+        };
+        let mut escaped = event.clone();
+        escaped.label = "kworker/\"0\"\n".to_string();
+        assert!(escaped
+            .to_json()
+            .contains("\"label\": \"kworker/\\\"0\\\"\\u000a\""));
+
+        AlertSink::Webhook(url).emit(&event).unwrap();
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /alerts HTTP/1.1\r\n"));
+        assert!(request.ends_with(&format!("\r\n\r\n{}", event.to_json())));
+    }
+}
@@ -0,0 +1,150 @@
+use crate::data::{Metric, Sample, Value};
+use chrono::Utc;
+use std::cmp::Ordering;
+use sysinfo::{Disks, Networks, ProcessesToUpdate, System};
+
+const MB: u64 = 1024 * 1024;
+
+/// Sistem, disk ve ağ bilgilerini tutarak istenen metrikleri toplar.
+///
+/// Cpu kullanımı ve ağ trafiği iki ölçüm arasındaki farka göre hesaplandığı için aynı
+/// nesne ile periyodik olarak ölçüm alınmalıdır.
+pub struct Collector {
+    system: System,
+    disks: Disks,
+    networks: Networks,
+}
+
+impl Collector {
+    pub fn new() -> Self {
+        let mut system = System::new();
+        system.refresh_all();
+        Collector {
+            system,
+            disks: Disks::new_with_refreshed_list(),
+            networks: Networks::new_with_refreshed_list(),
+        }
+    }
+
+    /// İstenen metrikler için birer ölçüm alır.
+    pub fn collect(&mut self, metrics: &[Metric], top: usize) -> Vec<Sample> {
+        if metrics
+            .iter()
+            .any(|metric| matches!(metric, Metric::TopCpu | Metric::TopMemory))
+        {
+            self.system.refresh_processes(ProcessesToUpdate::All, true);
+        }
+        metrics
+            .iter()
+            .map(|metric| {
+                let (unit, values) = match metric {
+                    Metric::Cpu => ("%", self.cpu()),
+                    Metric::Memory => ("MB", self.memory()),
+                    Metric::Swap => ("MB", self.swap()),
+                    Metric::Disk => ("%", self.disk()),
+                    Metric::Network => ("B", self.network()),
+                    Metric::Load => ("", load()),
+                    Metric::TopCpu => ("%", self.top_processes(top, true)),
+                    Metric::TopMemory => ("MB", self.top_processes(top, false)),
+                };
+                Sample {
+                    timestamp: Utc::now(),
+                    metric: metric.clone(),
+                    unit,
+                    values,
+                }
+            })
+            .collect()
+    }
+
+    fn cpu(&mut self) -> Vec<Value> {
+        self.system.refresh_cpu_usage();
+        self.system
+            .cpus()
+            .iter()
+            .enumerate()
+            .map(|(id, cpu)| Value::new(id.to_string(), cpu.cpu_usage() as f64))
+            .collect()
+    }
+
+    fn memory(&mut self) -> Vec<Value> {
+        self.system.refresh_memory();
+        vec![
+            Value::new("free", (self.system.free_memory() / MB) as f64),
+            Value::new("used", (self.system.used_memory() / MB) as f64),
+        ]
+    }
+
+    fn swap(&mut self) -> Vec<Value> {
+        self.system.refresh_memory();
+        vec![
+            Value::new("free", (self.system.free_swap() / MB) as f64),
+            Value::new("used", (self.system.used_swap() / MB) as f64),
+        ]
+    }
+
+    // Bağlama noktalarına göre doluluk yüzdesi
+    fn disk(&mut self) -> Vec<Value> {
+        self.disks.refresh(true);
+        self.disks
+            .iter()
+            .filter(|disk| disk.total_space() > 0)
+            .map(|disk| {
+                let used = disk.total_space() - disk.available_space();
+                Value::new(
+                    disk.mount_point().to_string_lossy(),
+                    used as f64 * 100.0 / disk.total_space() as f64,
+                )
+            })
+            .collect()
+    }
+
+    // Arayüzlere göre bir önceki ölçümden bu yana alınan ve gönderilen byte miktarı
+    fn network(&mut self) -> Vec<Value> {
+        self.networks.refresh(true);
+        let mut values = Vec::new();
+        for (interface, data) in self.networks.iter() {
+            values.push(Value::new(
+                format!("{} in", interface),
+                data.received() as f64,
+            ));
+            values.push(Value::new(
+                format!("{} out", interface),
+                data.transmitted() as f64,
+            ));
+        }
+        values.sort_by(|a, b| a.label.cmp(&b.label));
+        values
+    }
+
+    fn top_processes(&self, top: usize, by_cpu: bool) -> Vec<Value> {
+        let mut values: Vec<Value> = self
+            .system
+            .processes()
+            .values()
+            // Linux'ta thread'ler de process olarak listelenir
+            .filter(|process| process.thread_kind().is_none())
+            .map(|process| {
+                let label = format!("{} {}", process.pid(), process.name().to_string_lossy());
+                let value = if by_cpu {
+                    process.cpu_usage() as f64
+                } else {
+                    (process.memory() / MB) as f64
+                };
+                Value::new(label, value)
+            })
+            .collect();
+        values.sort_by(|a, b| b.value.partial_cmp(&a.value).unwrap_or(Ordering::Equal));
+        values.truncate(top);
+        values
+    }
+}
+
+fn load() -> Vec<Value> {
+    let load = System::load_average();
+    vec![
+        Value::new("1m", load.one),
+        Value::new("5m", load.five),
+        Value::new("15m", load.fifteen),
+    ]
+}
@@ -1,18 +1,80 @@
+use crate::alert::{AlertRule, AlertSink};
+use chrono::{DateTime, Utc};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Metric {
     Cpu,
     Memory,
-    Both,
+    Swap,
+    Disk,
+    Network,
+    Load,
+    /// En çok işlemci kullanan process'ler
+    TopCpu,
+    /// En çok bellek kullanan process'ler
+    TopMemory,
 }
 
 impl Metric {
+    pub const ALL: [Metric; 8] = [
+        Metric::Cpu,
+        Metric::Memory,
+        Metric::Swap,
+        Metric::Disk,
+        Metric::Network,
+        Metric::Load,
+        Metric::TopCpu,
+        Metric::TopMemory,
+    ];
+
     pub fn to_string(&self) -> &'static str {
         match self {
             Metric::Cpu => "CPU",
             Metric::Memory => "Memory",
-            Metric::Both => "Cpu and Memory",
+            Metric::Swap => "Swap",
+            Metric::Disk => "Disk",
+            Metric::Network => "Network",
+            Metric::Load => "Load",
+            Metric::TopCpu => "Top CPU",
+            Metric::TopMemory => "Top Memory",
+        }
+    }
+    /// Komut satırında kullanılan adı
+    pub fn key(&self) -> &'static str {
+        match self {
+            Metric::Cpu => "cpu",
+            Metric::Memory => "mem",
+            Metric::Swap => "swap",
+            Metric::Disk => "disk",
+            Metric::Network => "net",
+            Metric::Load => "load",
+            Metric::TopCpu => "top-cpu",
+            Metric::TopMemory => "top-mem",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Format {
+    Json,
+    Xml,
+    Csv,
+}
+
+impl Format {
+    pub fn to_string(&self) -> &'static str {
+        match self {
+            Format::Json => "JSON",
+            Format::Xml => "XML",
+            Format::Csv => "CSV",
+        }
+    }
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Xml => "xml",
+            Format::Csv => "csv",
         }
     }
 }
@@ -21,7 +83,50 @@ impl Metric {
 pub struct Command {
     pub count: u32,
     pub period: u8,
+    pub metrics: Vec<Metric>,
+    /// `TopCpu` ve `TopMemory` metriklerinde listelenen process sayısı
+    pub top: usize,
+    pub format: Option<Format>,
+    /// Verilmezse `metrics.<format>` dosyasına yazılır
+    pub output: Option<String>,
+    pub alerts: Vec<AlertRule>,
+    pub alert_sink: AlertSink,
+}
+
+impl Command {
+    pub fn output_file(&self) -> Option<String> {
+        let format = self.format.as_ref()?;
+        Some(
+            self.output
+                .clone()
+                .unwrap_or_else(|| format!("metrics.{}", format.extension())),
+        )
+    }
+}
+
+/// Tek bir ölçüm. Değerler ölçülen nesneye göre etiketlenir. Örneğin Cpu için çekirdek
+/// numarası, disk için bağlama noktası, ağ için arayüz adı kullanılır.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub timestamp: DateTime<Utc>,
     pub metric: Metric,
+    pub unit: &'static str,
+    pub values: Vec<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Value {
+    pub label: String,
+    pub value: f64,
+}
+
+impl Value {
+    pub fn new(label: impl Into<String>, value: f64) -> Self {
+        Value {
+            label: label.into(),
+            value,
+        }
+    }
 }
 
 // impl Command {
@@ -40,9 +145,17 @@ impl Display for Command {
         write!(
             f,
             "Metric {} Count : {} Period : {} secs",
-            self.metric.to_string(),
+            self.metrics
+                .iter()
+                .map(Metric::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
             self.count,
             self.period,
-        )
+        )?;
+        if let (Some(format), Some(file)) = (&self.format, self.output_file()) {
+            write!(f, " Format : {} Output : {}", format.to_string(), file)?;
+        }
+        Ok(())
     }
 }
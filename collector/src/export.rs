@@ -0,0 +1,241 @@
+use crate::data::{Format, Sample};
+use chrono::SecondsFormat;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+/// Ölçümleri toplandıkça istenen formatta yazar.
+///
+/// Her `write` çağrısından sonra veri diske aktarılır. Çalışma yarıda kesilirse dosyanın
+/// kapanış kısmı eksik kalır ama o ana kadar toplanan ölçümler kaybolmaz.
+pub struct SampleWriter<W: Write> {
+    writer: W,
+    format: Format,
+    count: usize,
+}
+
+impl SampleWriter<BufWriter<File>> {
+    pub fn create(path: &str, format: &Format) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?), format)
+    }
+}
+
+impl<W: Write> SampleWriter<W> {
+    pub fn new(mut writer: W, format: &Format) -> io::Result<Self> {
+        let header = match format {
+            Format::Json => "[",
+            Format::Xml => "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<samples>\n",
+            Format::Csv => "timestamp,metric,unit,label,value\n",
+        };
+        writer.write_all(header.as_bytes())?;
+        Ok(SampleWriter {
+            writer,
+            format: format.clone(),
+            count: 0,
+        })
+    }
+
+    /// Yazılan ölçüm sayısı
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn write(&mut self, samples: &[Sample]) -> io::Result<()> {
+        for sample in samples {
+            let record = match self.format {
+                Format::Json if self.count == 0 => format!("\n{}", json_record(sample)),
+                Format::Json => format!(",\n{}", json_record(sample)),
+                Format::Xml => xml_record(sample),
+                Format::Csv => csv_records(sample),
+            };
+            self.writer.write_all(record.as_bytes())?;
+            self.count += 1;
+        }
+        self.writer.flush()
+    }
+
+    /// Dosyanın kapanış kısmını yazar.
+    pub fn finish(mut self) -> io::Result<W> {
+        let footer = match self.format {
+            Format::Json if self.count == 0 => "]\n",
+            Format::Json => "\n]\n",
+            Format::Xml => "</samples>\n",
+            Format::Csv => "",
+        };
+        self.writer.write_all(footer.as_bytes())?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+fn timestamp(sample: &Sample) -> String {
+    sample
+        .timestamp
+        .to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn json_record(sample: &Sample) -> String {
+    let values: Vec<String> = sample
+        .values
+        .iter()
+        .map(|v| {
+            format!(
+                "{{\"label\": \"{}\", \"value\": {:.2}}}",
+                escape_json(&v.label),
+                v.value
+            )
+        })
+        .collect();
+    format!(
+        "  {{\"timestamp\": \"{}\", \"metric\": \"{}\", \"unit\": \"{}\", \"values\": [{}]}}",
+        timestamp(sample),
+        sample.metric.to_string(),
+        sample.unit,
+        values.join(", ")
+    )
+}
+
+fn xml_record(sample: &Sample) -> String {
+    let mut xml = format!(
+        "  <sample timestamp=\"{}\" metric=\"{}\" unit=\"{}\">\n",
+        timestamp(sample),
+        sample.metric.to_string(),
+        escape_xml(sample.unit)
+    );
+    for value in &sample.values {
+        xml.push_str(&format!(
+            "    <value label=\"{}\">{:.2}</value>\n",
+            escape_xml(&value.label),
+            value.value
+        ));
+    }
+    xml.push_str("  </sample>\n");
+    xml
+}
+
+/// Her satırda tek bir değer bulunur.
+fn csv_records(sample: &Sample) -> String {
+    let mut csv = String::new();
+    for value in &sample.values {
+        csv.push_str(&format!(
+            "{},{},{},{},{:.2}\n",
+            timestamp(sample),
+            sample.metric.to_string(),
+            sample.unit,
+            escape_csv(&value.label),
+            value.value
+        ));
+    }
+    csv
+}
+
+pub(crate) fn escape_json(value: &str) -> String {
+    let mut escaped = String::new();
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        return format!("\"{}\"", value.replace('"', "\"\""));
+    }
+    value.to_string()
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{Metric, Value};
+    use chrono::{TimeZone, Utc};
+
+    fn export(samples: &[Sample], format: &Format) -> String {
+        // Bellekteki tampona yazmak hata vermez
+        let mut writer = SampleWriter::new(Vec::new(), format).unwrap();
+        writer.write(samples).unwrap();
+        String::from_utf8(writer.finish().unwrap()).unwrap_or_default()
+    }
+
+    fn samples() -> Vec<Sample> {
+        let timestamp = Utc.with_ymd_and_hms(2025, 3, 1, 10, 30, 0).unwrap();
+        vec![
+            Sample {
+                timestamp,
+                metric: Metric::Cpu,
+                unit: "%",
+                values: vec![Value::new("0", 12.5), Value::new("1", 3.0)],
+            },
+            Sample {
+                timestamp,
+                metric: Metric::TopMemory,
+                unit: "MB",
+                values: vec![Value::new("42 \"my, app\"", 2048.0)],
+            },
+        ]
+    }
+
+    #[test]
+    fn should_export_samples_as_json_test() {
+        assert_eq!(
+            export(&samples(), &Format::Json),
+            "[\n  {\"timestamp\": \"2025-03-01T10:30:00.000Z\", \"metric\": \"CPU\", \"unit\": \"%\", \
+             \"values\": [{\"label\": \"0\", \"value\": 12.50}, {\"label\": \"1\", \"value\": 3.00}]},\n  \
+             {\"timestamp\": \"2025-03-01T10:30:00.000Z\", \"metric\": \"Top Memory\", \"unit\": \"MB\", \
+             \"values\": [{\"label\": \"42 \\\"my, app\\\"\", \"value\": 2048.00}]}\n]\n"
+        );
+        assert_eq!(export(&[], &Format::Json), "[]\n");
+    }
+
+    #[test]
+    fn should_export_samples_as_xml_and_csv_test() {
+        let xml = export(&samples(), &Format::Xml);
+        assert!(xml.contains(
+            "  <sample timestamp=\"2025-03-01T10:30:00.000Z\" metric=\"CPU\" unit=\"%\">\n    \
+             <value label=\"0\">12.50</value>\n    <value label=\"1\">3.00</value>\n  </sample>\n"
+        ));
+        assert!(xml.contains("<value label=\"42 &quot;my, app&quot;\">2048.00</value>"));
+        assert!(xml.ends_with("</samples>\n"));
+        assert_eq!(
+            export(&[], &Format::Xml),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<samples>\n</samples>\n"
+        );
+
+        assert_eq!(
+            export(&samples(), &Format::Csv),
+            "timestamp,metric,unit,label,value\n\
+             2025-03-01T10:30:00.000Z,CPU,%,0,12.50\n\
+             2025-03-01T10:30:00.000Z,CPU,%,1,3.00\n\
+             2025-03-01T10:30:00.000Z,Top Memory,MB,\"42 \"\"my, app\"\"\",2048.00\n"
+        );
+    }
+
+    #[test]
+    fn should_append_each_batch_as_it_is_written_test() {
+        let mut writer = SampleWriter::new(Vec::new(), &Format::Csv).unwrap();
+        let samples = samples();
+        writer.write(&samples[..1]).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&writer.writer),
+            "timestamp,metric,unit,label,value\n\
+             2025-03-01T10:30:00.000Z,CPU,%,0,12.50\n\
+             2025-03-01T10:30:00.000Z,CPU,%,1,3.00\n"
+        );
+        writer.write(&samples[1..]).unwrap();
+        assert_eq!(writer.count(), 2);
+        let csv = String::from_utf8(writer.finish().unwrap()).unwrap();
+        assert_eq!(csv, export(&samples, &Format::Csv));
+    }
+}
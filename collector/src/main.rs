@@ -1,11 +1,15 @@
-use crate::data::Metric;
-use crate::terminal::parse;
-use crate::view::{print_all_metrics, print_cpu, print_memory, show_usages};
+use crate::alert::{print_summary, AlertMonitor};
+use crate::collect::Collector;
+use crate::export::SampleWriter;
+use crate::terminal::{parse, ParseError};
+use crate::view::{print_sample, show_usages};
 use std::thread::sleep;
 use std::time::Duration;
-use sysinfo::System;
 
+mod alert;
+mod collect;
 mod data;
+mod export;
 mod terminal;
 mod view;
 
@@ -14,27 +18,56 @@ fn main() {
         Ok(cmd) => {
             println!("{cmd}");
 
-            let mut system = System::new();
-            system.refresh_all();
+            // Format verilmişse ölçümler toplandıkça dosyaya eklenir
+            let mut output = match (&cmd.format, cmd.output_file()) {
+                (Some(format), Some(file)) => match SampleWriter::create(&file, format) {
+                    Ok(writer) => Some((writer, file)),
+                    Err(e) => save_failed(&file, e),
+                },
+                _ => None,
+            };
 
+            let mut collector = Collector::new();
+            let mut monitor = AlertMonitor::new(cmd.alerts.clone());
             for _ in 0..cmd.count {
-                match cmd.metric {
-                    Metric::Memory => {
-                        print_memory(&mut system);
+                let samples = collector.collect(&cmd.metrics, cmd.top);
+                for sample in &samples {
+                    print_sample(sample);
+                    for event in monitor.evaluate(sample) {
+                        if let Err(e) = cmd.alert_sink.emit(&event) {
+                            eprintln!("Error: Alert could not be sent. {}", e);
+                        }
                     }
-                    Metric::Cpu => {
-                        print_cpu(&mut system);
-                    }
-                    Metric::Both => {
-                        print_all_metrics(&mut system);
+                }
+                if let Some((writer, file)) = output.as_mut() {
+                    if let Err(e) = writer.write(&samples) {
+                        save_failed(file, e);
                     }
                 }
                 sleep(Duration::from_secs(cmd.period as u64));
             }
+            if !cmd.alerts.is_empty() {
+                print_summary(&monitor.summary());
+            }
+
+            if let Some((writer, file)) = output {
+                let count = writer.count();
+                match writer.finish() {
+                    Ok(_) => println!("{} samples saved to {}", count, file),
+                    Err(e) => save_failed(&file, e),
+                }
+            }
         }
+        Err(ParseError::HelpRequested) => show_usages(),
         Err(e) => {
             eprintln!("Error: {}", e);
             show_usages();
+            std::process::exit(2);
         }
     }
 }
+
+fn save_failed(file: &str, error: std::io::Error) -> ! {
+    eprintln!("Error: Samples could not be saved to {}. {}", file, error);
+    std::process::exit(1);
+}
@@ -1,32 +1,380 @@
+use crate::alert::{AlertRule, AlertSink};
 use crate::data::*;
 use std::env;
+use std::fmt::{Display, Formatter};
 
-pub fn parse() -> Result<Command, String> {
-    let args: Vec<String> = env::args().collect();
-    // println!("{:?}", args);
+/// Komut satırında kabul edilen bir seçeneğin tanımı. Yardım metni de bu tanımlardan üretilir.
+pub struct OptionDef {
+    pub name: &'static str,
+    pub short: char,
+    pub value: &'static str,
+    pub help: &'static str,
+    /// Hatalı değerlerde gösterilen beklenen değer açıklaması
+    pub expected: &'static str,
+    pub default: &'static str,
+    /// Birden fazla kez verilebilir
+    pub multiple: bool,
+}
 
-    // early return
-    if args.len() < 2 {
-        return Err("Wrong number of arguments".to_string());
-    }
+pub const OPTIONS: [OptionDef; 8] = [
+    OptionDef {
+        name: "kind",
+        short: 'k',
+        value: "METRIC,...",
+        help: "Metrics to collect: cpu, mem, swap, disk, net, load, top-cpu, top-mem or all",
+        expected:
+            "a comma separated list of cpu, mem, swap, disk, net, load, top-cpu, top-mem or all",
+        default: "cpu,mem",
+        multiple: false,
+    },
+    OptionDef {
+        name: "count",
+        short: 'c',
+        value: "NUMBER",
+        help: "Number of total metric (1 or more)",
+        expected: "a number greater than 0",
+        default: "20",
+        multiple: false,
+    },
+    OptionDef {
+        name: "period",
+        short: 'p',
+        value: "SECONDS",
+        help: "Number of periodic metric in seconds (1-255)",
+        expected: "a number between 1 and 255",
+        default: "1",
+        multiple: false,
+    },
+    OptionDef {
+        name: "top",
+        short: 't',
+        value: "NUMBER",
+        help: "Number of processes listed by top-cpu and top-mem",
+        expected: "a number greater than 0",
+        default: "5",
+        multiple: false,
+    },
+    OptionDef {
+        name: "format",
+        short: 'f',
+        value: "json|xml|csv",
+        help: "Save collected metrics to a file in this format",
+        expected: "json, xml or csv",
+        default: "",
+        multiple: false,
+    },
+    OptionDef {
+        name: "output",
+        short: 'o',
+        value: "FILE",
+        help: "File to save metrics into",
+        expected: "a file name",
+        default: "metrics.<format>",
+        multiple: false,
+    },
+    OptionDef {
+        name: "alert",
+        short: 'a',
+        value: "RULE",
+        help: "Alert rule like 'cpu>90,for=10,hysteresis=5,level=alarm' (repeatable, quote it in the shell)",
+        expected:
+            "METRIC[:LABEL]>THRESHOLD[,for=SECONDS][,hysteresis=N][,level=warm|burning|alarm]",
+        default: "",
+        multiple: true,
+    },
+    OptionDef {
+        name: "alert-to",
+        short: 'A',
+        value: "SINK",
+        help: "Where alert events go: stderr, file:PATH or webhook:http://host:port/path",
+        expected: "stderr, file:PATH or webhook:http://host:port/path",
+        default: "stderr",
+        multiple: false,
+    },
+];
 
-    if args[1] != "kind" {
-        return Err("Invalid kind".to_string());
-    }
-    if args[3] != "count" {
-        return Err("Invalid count".to_string());
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    HelpRequested,
+    UnknownOption(String),
+    MissingValue(&'static str),
+    Duplicate(&'static str),
+    InvalidRule {
+        rule: String,
+        reason: String,
+    },
+    InvalidValue {
+        option: &'static str,
+        value: String,
+        expected: &'static str,
+    },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::HelpRequested => write!(f, "Help requested"),
+            ParseError::UnknownOption(arg) => write!(f, "Unknown option '{}'", arg),
+            ParseError::MissingValue(option) => write!(f, "Missing value for '{}'", option),
+            ParseError::Duplicate(option) => write!(f, "'{}' is given more than once", option),
+            ParseError::InvalidRule { rule, reason } => {
+                write!(f, "Invalid alert rule '{}'. {}", rule, reason)
+            }
+            ParseError::InvalidValue {
+                option,
+                value,
+                expected,
+            } => write!(
+                f,
+                "Invalid value '{}' for '{}'. Expected {}",
+                value, option, expected
+            ),
+        }
     }
-    if args[5] != "period" {
-        return Err("Invalid period".to_string());
+}
+
+pub fn parse() -> Result<Command, ParseError> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    parse_args(&args)
+}
+
+/// Seçenekler herhangi bir sırada verilebilir. Her seçenek şu şekillerde yazılabilir:
+///
+/// `kind cpu`, `--kind cpu`, `--kind=cpu`, `-k cpu`
+pub fn parse_args(args: &[String]) -> Result<Command, ParseError> {
+    let mut values: [Vec<String>; OPTIONS.len()] = Default::default();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        if matches!(arg.as_str(), "help" | "--help" | "-h") {
+            return Err(ParseError::HelpRequested);
+        }
+        let (key, inline_value) = match arg.strip_prefix("--") {
+            Some(long) => match long.split_once('=') {
+                Some((key, value)) => (key, Some(value.to_string())),
+                None => (long, None),
+            },
+            None => (arg.as_str(), None),
+        };
+        let index = OPTIONS
+            .iter()
+            .position(|option| {
+                option.name == key
+                    || (key.len() == 2 && key.starts_with('-') && key.ends_with(option.short))
+            })
+            .ok_or_else(|| ParseError::UnknownOption(arg.clone()))?;
+
+        let name = OPTIONS[index].name;
+        if !OPTIONS[index].multiple && !values[index].is_empty() {
+            return Err(ParseError::Duplicate(name));
+        }
+        let value = match inline_value {
+            Some(value) => value,
+            None => args.next().cloned().ok_or(ParseError::MissingValue(name))?,
+        };
+        values[index].push(value);
     }
 
+    let given = |index: usize| values[index].first().cloned();
+    let value = |index: usize| given(index).unwrap_or_else(|| OPTIONS[index].default.to_string());
     Ok(Command {
-        count: args[4].parse::<u32>().unwrap_or(20),
-        period: args[6].parse::<u8>().unwrap_or(1),
-        metric: match args[2].to_lowercase().as_str() {
-            "cpu" => Metric::Cpu,
-            "mem" => Metric::Memory,
-            _ => Metric::Both,
+        metrics: parse_metrics(&value(0))?,
+        count: parse_number(&OPTIONS[1], &value(1))?,
+        period: parse_number(&OPTIONS[2], &value(2))?,
+        top: parse_number(&OPTIONS[3], &value(3))?,
+        format: match given(4) {
+            Some(format) => Some(parse_format(&format)?),
+            None => None,
         },
+        output: match given(5) {
+            Some(output) if output.trim().is_empty() => return Err(invalid(&OPTIONS[5], &output)),
+            output => output,
+        },
+        alerts: values[6]
+            .iter()
+            .map(|rule| {
+                AlertRule::parse(rule).map_err(|reason| ParseError::InvalidRule {
+                    rule: rule.clone(),
+                    reason,
+                })
+            })
+            .collect::<Result<_, _>>()?,
+        alert_sink: AlertSink::parse(&value(7)).map_err(|_| invalid(&OPTIONS[7], &value(7)))?,
     })
 }
+
+// Tekrar eden metrikler bir kez toplanır
+fn parse_metrics(value: &str) -> Result<Vec<Metric>, ParseError> {
+    let mut metrics = Vec::new();
+    for name in value.split(',').map(|name| name.trim().to_lowercase()) {
+        let selected: Vec<Metric> = match name.as_str() {
+            "all" => Metric::ALL.to_vec(),
+            "memory" => vec![Metric::Memory],
+            _ => Metric::ALL
+                .iter()
+                .filter(|metric| metric.key() == name)
+                .cloned()
+                .collect(),
+        };
+        if selected.is_empty() {
+            return Err(invalid(&OPTIONS[0], value));
+        }
+        for metric in selected {
+            if !metrics.contains(&metric) {
+                metrics.push(metric);
+            }
+        }
+    }
+    Ok(metrics)
+}
+
+fn parse_format(value: &str) -> Result<Format, ParseError> {
+    match value.to_lowercase().as_str() {
+        "json" => Ok(Format::Json),
+        "xml" => Ok(Format::Xml),
+        "csv" => Ok(Format::Csv),
+        _ => Err(invalid(&OPTIONS[4], value)),
+    }
+}
+
+// Sıfır değerine izin verilmez
+fn parse_number<T: std::str::FromStr + Default + PartialEq>(
+    option: &OptionDef,
+    value: &str,
+) -> Result<T, ParseError> {
+    match value.parse::<T>() {
+        Ok(number) if number != T::default() => Ok(number),
+        _ => Err(invalid(option, value)),
+    }
+}
+
+fn invalid(option: &OptionDef, value: &str) -> ParseError {
+    ParseError::InvalidValue {
+        option: option.name,
+        value: value.to_string(),
+        expected: option.expected,
+    }
+}
+
+/// `OPTIONS` tanımlarından yardım metnini üretir.
+pub fn usage() -> String {
+    let mut text = String::from(
+        "A tiny system metrics collector for cpu, memory, swap, disk, network, load and top \
+         processes,\nwith export to json, xml or csv and threshold alerts\n\nOptions :\n\n",
+    );
+    for option in OPTIONS.iter() {
+        let flags = format!("-{}, --{} <{}>", option.short, option.name, option.value);
+        text.push_str(&format!("    {:<32}{}", flags, option.help));
+        if !option.default.is_empty() {
+            text.push_str(&format!(" [default: {}]", option.default));
+        }
+        text.push('\n');
+    }
+    text.push_str(
+        "    -h, --help                      Show this help\n\n\
+         Usages  :\n\n    \
+         kind cpu count 100 period 2 format json\n    \
+         --period 2 -c 100 --kind=mem -f csv -o memory.csv\n    \
+         kind disk,net,top-mem --top 10\n    \
+         -k cpu,mem -a 'cpu>90,for=10,hysteresis=5' -a 'mem:free<500' -A file:alerts.log\n",
+    );
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn should_parse_options_in_any_order_and_style_test() {
+        let command = parse_args(&args("period 2 -c 100 --kind=mem --format xml")).unwrap();
+        assert_eq!(command.metrics, vec![Metric::Memory]);
+        assert_eq!(command.count, 100);
+        assert_eq!(command.period, 2);
+        assert_eq!(command.format, Some(Format::Xml));
+
+        assert_eq!(command.output_file().as_deref(), Some("metrics.xml"));
+
+        let command = parse_args(&args("kind net,cpu,net -t 3")).unwrap();
+        assert_eq!(command.metrics, vec![Metric::Network, Metric::Cpu]);
+        assert_eq!(command.top, 3);
+        assert_eq!(
+            parse_args(&[]).unwrap().metrics,
+            vec![Metric::Cpu, Metric::Memory]
+        );
+        assert_eq!(
+            parse_args(&args("-k all")).unwrap().metrics,
+            Metric::ALL.to_vec()
+        );
+
+        let command = parse_args(&args("kind cpu")).unwrap();
+        assert_eq!(command.metrics, vec![Metric::Cpu]);
+        assert!(command.alerts.is_empty());
+        assert_eq!(command.alert_sink, AlertSink::Stderr);
+
+        let command = parse_args(&args("-a cpu>90 --alert mem:free<500 -A file:a.log")).unwrap();
+        assert_eq!(command.alerts.len(), 2);
+        assert_eq!(command.alerts[1].metric, Metric::Memory);
+        assert_eq!(command.alert_sink, AlertSink::File("a.log".to_string()));
+        assert_eq!(
+            (command.count, command.period, command.format),
+            (20, 1, None)
+        );
+    }
+
+    #[test]
+    fn should_reject_bad_values_test() {
+        assert_eq!(
+            parse_args(&args("count")).unwrap_err(),
+            ParseError::MissingValue("count")
+        );
+        assert_eq!(
+            parse_args(&args("kind cpu,gpu")).unwrap_err().to_string(),
+            "Invalid value 'cpu,gpu' for 'kind'. Expected a comma separated list of cpu, mem, \
+             swap, disk, net, load, top-cpu, top-mem or all"
+        );
+        assert!(matches!(
+            parse_args(&args("-p 300")),
+            Err(ParseError::InvalidValue {
+                option: "period",
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_args(&args("--count 0")),
+            Err(ParseError::InvalidValue {
+                option: "count",
+                ..
+            })
+        ));
+        assert_eq!(
+            parse_args(&args("-k cpu --kind mem")).unwrap_err(),
+            ParseError::Duplicate("kind")
+        );
+        assert_eq!(
+            parse_args(&args("-a cpu>x")).unwrap_err().to_string(),
+            "Invalid alert rule 'cpu>x'. threshold 'x' is not a positive number"
+        );
+        assert_eq!(
+            parse_args(&args("--verbose")).unwrap_err(),
+            ParseError::UnknownOption("--verbose".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("kind cpu --help")).unwrap_err(),
+            ParseError::HelpRequested
+        );
+    }
+
+    #[test]
+    fn should_generate_usage_from_definitions_test() {
+        let usage = usage();
+        for option in OPTIONS.iter() {
+            assert!(usage.contains(&format!("-{}, --{}", option.short, option.name)));
+        }
+        assert!(usage.contains("[default: 20]"));
+    }
+}
@@ -0,0 +1,326 @@
+use crate::ecs::World;
+use crate::framework::Actor;
+use crate::message::BotCommand;
+
+/// Bir düğümün tick sonucunda döndüğü durum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Success,
+    Failure,
+    /// Düğüm işini bitirmedi, bir sonraki tick'te kaldığı yerden devam eder
+    Running,
+}
+
+impl From<bool> for Status {
+    fn from(value: bool) -> Self {
+        if value {
+            Status::Success
+        } else {
+            Status::Failure
+        }
+    }
+}
+
+/// Davranış ağacına her tick'te geçilen bağlam.
+///
+/// Dünya sadece okunabilir. Aktörler üzerindeki değişiklikler komutlar ile talep edilir ve
+/// ağaç çalışmasını bitirdikten sonra aynı update içerisinde aktörlere iletilir.
+pub struct BehaviourContext<'a> {
+    bot: usize,
+    world: &'a World,
+    delta_time: f32,
+    commands: Vec<BotCommand>,
+}
+
+impl<'a> BehaviourContext<'a> {
+    pub fn new(bot: usize, world: &'a World, delta_time: f32) -> Self {
+        BehaviourContext {
+            bot,
+            world,
+            delta_time,
+            commands: Vec::new(),
+        }
+    }
+    /// Ağacı çalıştıran bot'un sıra numarası
+    pub fn bot(&self) -> usize {
+        self.bot
+    }
+    pub fn world(&self) -> &'a World {
+        self.world
+    }
+    pub fn delta_time(&self) -> f32 {
+        self.delta_time
+    }
+    pub fn find_actor(&self, actor_id: u32) -> Option<&'a dyn Actor> {
+        self.world.find_actor(actor_id)
+    }
+    /// Verilen türdeki tüm aktörleri döner
+    pub fn actors_of_kind(&self, kind: &'a str) -> impl Iterator<Item = &'a dyn Actor> + 'a {
+        self.world
+            .actors
+            .iter()
+            .map(|(_, actor)| actor.as_ref())
+            .filter(move |actor| actor.kind() == kind)
+    }
+    pub fn send(&mut self, command: BotCommand) {
+        self.commands.push(command);
+    }
+    pub fn into_commands(self) -> Vec<BotCommand> {
+        self.commands
+    }
+}
+
+/// Davranış ağacını oluşturan düğümler.
+pub trait Node {
+    fn tick(&mut self, context: &mut BehaviourContext) -> Status;
+    /// Düğümün ve alt düğümlerinin ara durumlarını temizler
+    fn reset(&mut self) {}
+}
+
+/// Alt düğümleri sırayla çalıştırır. Biri başarısız olursa durur ve başarısız döner.
+///
+/// `Running` dönen alt düğüm hatırlanır, bir sonraki tick o düğümden devam eder.
+pub struct Sequence {
+    children: Vec<Box<dyn Node>>,
+    current: usize,
+}
+
+impl Sequence {
+    pub fn new(children: Vec<Box<dyn Node>>) -> Self {
+        Sequence {
+            children,
+            current: 0,
+        }
+    }
+}
+
+impl Node for Sequence {
+    fn tick(&mut self, context: &mut BehaviourContext) -> Status {
+        while let Some(child) = self.children.get_mut(self.current) {
+            match child.tick(context) {
+                Status::Success => self.current += 1,
+                Status::Running => return Status::Running,
+                Status::Failure => {
+                    self.reset();
+                    return Status::Failure;
+                }
+            }
+        }
+        self.reset();
+        Status::Success
+    }
+    fn reset(&mut self) {
+        self.current = 0;
+        for child in &mut self.children {
+            child.reset();
+        }
+    }
+}
+
+/// Alt düğümleri sırayla dener, ilk başarılı olanda durur. Hiçbiri başarılı olmazsa
+/// başarısız döner.
+pub struct Selector {
+    children: Vec<Box<dyn Node>>,
+    current: usize,
+}
+
+impl Selector {
+    pub fn new(children: Vec<Box<dyn Node>>) -> Self {
+        Selector {
+            children,
+            current: 0,
+        }
+    }
+}
+
+impl Node for Selector {
+    fn tick(&mut self, context: &mut BehaviourContext) -> Status {
+        while let Some(child) = self.children.get_mut(self.current) {
+            match child.tick(context) {
+                Status::Failure => self.current += 1,
+                Status::Running => return Status::Running,
+                Status::Success => {
+                    self.reset();
+                    return Status::Success;
+                }
+            }
+        }
+        self.reset();
+        Status::Failure
+    }
+    fn reset(&mut self) {
+        self.current = 0;
+        for child in &mut self.children {
+            child.reset();
+        }
+    }
+}
+
+/// Dünyanın durumunu kontrol eden düğüm. Koşul sağlanıyorsa başarılı döner.
+pub struct Condition<F> {
+    check: F,
+}
+
+impl<F> Condition<F>
+where
+    F: FnMut(&BehaviourContext) -> bool,
+{
+    pub fn new(check: F) -> Self {
+        Condition { check }
+    }
+}
+
+impl<F> Node for Condition<F>
+where
+    F: FnMut(&BehaviourContext) -> bool,
+{
+    fn tick(&mut self, context: &mut BehaviourContext) -> Status {
+        (self.check)(context).into()
+    }
+}
+
+/// Komut gönderen veya bot'un kendi durumunu değiştiren düğüm.
+pub struct Action<F> {
+    run: F,
+}
+
+impl<F> Action<F>
+where
+    F: FnMut(&mut BehaviourContext) -> Status,
+{
+    pub fn new(run: F) -> Self {
+        Action { run }
+    }
+}
+
+impl<F> Node for Action<F>
+where
+    F: FnMut(&mut BehaviourContext) -> Status,
+{
+    fn tick(&mut self, context: &mut BehaviourContext) -> Status {
+        (self.run)(context)
+    }
+}
+
+/// Verilen süre dolana kadar `Running`, dolduğunda başarılı döner.
+pub struct Wait {
+    seconds: f32,
+    elapsed: f32,
+}
+
+impl Wait {
+    pub fn new(seconds: f32) -> Self {
+        Wait {
+            seconds,
+            elapsed: 0.0,
+        }
+    }
+}
+
+impl Node for Wait {
+    fn tick(&mut self, context: &mut BehaviourContext) -> Status {
+        self.elapsed += context.delta_time();
+        if self.elapsed + f32::EPSILON >= self.seconds {
+            self.elapsed = 0.0;
+            Status::Success
+        } else {
+            Status::Running
+        }
+    }
+    fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+}
+
+/// Bot'un davranışını tanımlayan ağaç. Oyunun thread'inde her update'te bir kez çalıştırılır.
+pub struct BehaviourTree {
+    root: Box<dyn Node>,
+}
+
+impl BehaviourTree {
+    pub fn new(root: Box<dyn Node>) -> Self {
+        BehaviourTree { root }
+    }
+    pub fn tick(&mut self, context: &mut BehaviourContext) -> Status {
+        self.root.tick(context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::UpdateContext;
+    use crate::render::Renderer;
+
+    struct Coin {
+        id: u32,
+    }
+
+    impl Actor for Coin {
+        fn id(&self) -> u32 {
+            self.id
+        }
+        fn kind(&self) -> &'static str {
+            "coin"
+        }
+        fn draw(&self, _renderer: &mut dyn Renderer) {}
+        fn update(&mut self, _context: &mut UpdateContext) {}
+    }
+
+    fn tick(tree: &mut BehaviourTree, world: &World) -> (Status, Vec<BotCommand>) {
+        let mut context = BehaviourContext::new(0, world, 0.5);
+        let status = tree.tick(&mut context);
+        (status, context.into_commands())
+    }
+
+    fn boost(actor_id: u32) -> BotCommand {
+        BotCommand::ChangeStrength {
+            actor_id,
+            amount: 1,
+        }
+    }
+
+    #[test]
+    fn should_resume_running_sequence_test() {
+        let mut world = World::new();
+        world.spawn_actor(Box::new(Coin { id: 3 }));
+        let mut tree = BehaviourTree::new(Box::new(Sequence::new(vec![
+            Box::new(Condition::new(|context: &BehaviourContext| {
+                context.actors_of_kind("coin").count() > 0
+            })),
+            Box::new(Wait::new(1.0)),
+            Box::new(Action::new(|context: &mut BehaviourContext| {
+                context.send(boost(3));
+                Status::Success
+            })),
+        ])));
+
+        assert_eq!(tick(&mut tree, &world), (Status::Running, vec![]));
+        assert_eq!(tick(&mut tree, &world), (Status::Success, vec![boost(3)]));
+
+        let empty = World::new();
+        assert_eq!(tick(&mut tree, &empty), (Status::Failure, vec![]));
+    }
+
+    #[test]
+    fn should_fall_back_to_next_child_in_selector_test() {
+        let world = World::new();
+        let mut tree = BehaviourTree::new(Box::new(Selector::new(vec![
+            Box::new(Sequence::new(vec![
+                Box::new(Condition::new(|context: &BehaviourContext| {
+                    context.find_actor(3).is_some()
+                })),
+                Box::new(Action::new(|context: &mut BehaviourContext| {
+                    context.send(boost(3));
+                    Status::Success
+                })),
+            ])),
+            Box::new(Action::new(|context: &mut BehaviourContext| {
+                context.send(boost(9));
+                Status::Success
+            })),
+        ])));
+
+        assert_eq!(tick(&mut tree, &world), (Status::Success, vec![boost(9)]));
+    }
+}
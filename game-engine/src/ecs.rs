@@ -0,0 +1,331 @@
+use crate::framework::{Actor, UpdateContext};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Dünyadaki her nesneyi temsil eden benzersiz id değeri.
+///
+/// Entity'nin kendisi bir davranış içermez, sahip olduğu bileşenler (component) ile anlam kazanır.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Entity(u32);
+
+impl Entity {
+    pub fn id(&self) -> u32 {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Position {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Velocity {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sprite {
+    pub glyph: char,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Health {
+    pub current: i32,
+    pub max: i32,
+}
+
+impl Health {
+    pub fn new(max: i32) -> Self {
+        Health { current: max, max }
+    }
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0
+    }
+}
+
+/// Belli bir türdeki bileşenleri entity'lere göre saklayan koleksiyon.
+///
+/// Sıralı bir map kullanıldığı için sistemler entity'leri her zaman aynı sırada dolaşır.
+pub struct Storage<T> {
+    items: BTreeMap<Entity, T>,
+}
+
+impl<T> Default for Storage<T> {
+    fn default() -> Self {
+        Storage {
+            items: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> Storage<T> {
+    pub fn insert(&mut self, entity: Entity, component: T) -> Option<T> {
+        self.items.insert(entity, component)
+    }
+    pub fn remove(&mut self, entity: Entity) -> Option<T> {
+        self.items.remove(&entity)
+    }
+    pub fn get(&self, entity: Entity) -> Option<&T> {
+        self.items.get(&entity)
+    }
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        self.items.get_mut(&entity)
+    }
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.items.contains_key(&entity)
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &T)> {
+        self.items.iter().map(|(entity, item)| (*entity, item))
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, &mut T)> {
+        self.items.iter_mut().map(|(entity, item)| (*entity, item))
+    }
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// Entity'leri ve onlara ait bileşen koleksiyonlarını barındırır.
+///
+/// `actors` koleksiyonu, `Actor` trait'ini uygulayan eski nesnelerin de birer entity olarak
+/// dünyada yaşayabilmesi için kullanılan adaptördür.
+#[derive(Default)]
+pub struct World {
+    next_id: u32,
+    entities: Vec<Entity>,
+    pub positions: Storage<Position>,
+    pub velocities: Storage<Velocity>,
+    pub sprites: Storage<Sprite>,
+    pub healths: Storage<Health>,
+    pub actors: Storage<Box<dyn Actor>>,
+    /// Entity'leri gruplamak için kullanılan etiketler. Aktörler türleri ile etiketlenir.
+    pub tags: Storage<BTreeSet<String>>,
+}
+
+impl World {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn spawn(&mut self) -> Entity {
+        let entity = self.reserve();
+        self.entities.push(entity);
+        entity
+    }
+    /// Dünyaya eklenmeden yeni bir entity değeri ayırır. Ertelenen eklemelerde
+    /// entity'nin önceden bilinebilmesi için kullanılır.
+    pub fn reserve(&mut self) -> Entity {
+        let entity = Entity(self.next_id);
+        self.next_id += 1;
+        entity
+    }
+    pub fn spawn_actor(&mut self, actor: Box<dyn Actor>) -> Entity {
+        let entity = self.reserve();
+        self.insert_actor(entity, actor);
+        entity
+    }
+    /// Aktörü önceden ayrılmış entity ile dünyaya ekler ve türü ile etiketler.
+    pub fn insert_actor(&mut self, entity: Entity, actor: Box<dyn Actor>) {
+        if !self.is_alive(entity) {
+            self.entities.push(entity);
+        }
+        self.tag(entity, actor.kind());
+        self.actors.insert(entity, actor);
+    }
+    /// Entity'yi ve sahip olduğu tüm bileşenleri dünyadan kaldırır.
+    pub fn despawn(&mut self, entity: Entity) -> bool {
+        let Some(index) = self.entities.iter().position(|e| *e == entity) else {
+            return false;
+        };
+        self.entities.remove(index);
+        self.positions.remove(entity);
+        self.velocities.remove(entity);
+        self.sprites.remove(entity);
+        self.healths.remove(entity);
+        self.actors.remove(entity);
+        self.tags.remove(entity);
+        true
+    }
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.entities.contains(&entity)
+    }
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+    pub fn tag(&mut self, entity: Entity, tag: &str) {
+        match self.tags.get_mut(entity) {
+            Some(tags) => {
+                tags.insert(tag.to_string());
+            }
+            None => {
+                self.tags.insert(entity, BTreeSet::from([tag.to_string()]));
+            }
+        }
+    }
+    pub fn untag(&mut self, entity: Entity, tag: &str) {
+        if let Some(tags) = self.tags.get_mut(entity) {
+            tags.remove(tag);
+        }
+    }
+    pub fn has_tag(&self, entity: Entity, tag: &str) -> bool {
+        self.tags.get(entity).is_some_and(|tags| tags.contains(tag))
+    }
+    /// Verilen etikete sahip entity'leri sırayla döner
+    pub fn tagged<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = Entity> + 'a {
+        self.tags
+            .iter()
+            .filter(move |(_, tags)| tags.contains(tag))
+            .map(|(entity, _)| entity)
+    }
+    pub fn actor(&self, entity: Entity) -> Option<&dyn Actor> {
+        self.actors.get(entity).map(|actor| actor.as_ref())
+    }
+    /// Aktör id değerine göre aktörün entity değerini bulur
+    pub fn entity_of(&self, actor_id: u32) -> Option<Entity> {
+        self.actors
+            .iter()
+            .find(|(_, actor)| actor.id() == actor_id)
+            .map(|(entity, _)| entity)
+    }
+    /// Aktör id değerine göre aktörü bulur
+    pub fn find_actor(&self, actor_id: u32) -> Option<&dyn Actor> {
+        self.actors
+            .iter()
+            .map(|(_, actor)| actor.as_ref())
+            .find(|actor| actor.id() == actor_id)
+    }
+    pub fn find_actor_mut(&mut self, actor_id: u32) -> Option<&mut Box<dyn Actor>> {
+        self.actors
+            .iter_mut()
+            .map(|(_, actor)| actor)
+            .find(|actor| actor.id() == actor_id)
+    }
+}
+
+/// Her frame'de dünya üzerinde çalıştırılan davranışlar.
+pub trait System {
+    fn run(&mut self, world: &mut World, context: &mut UpdateContext);
+}
+
+/// `Actor` nesnelerinin update fonksiyonlarını çağıran adaptör sistem.
+pub struct ActorSystem;
+
+impl System for ActorSystem {
+    fn run(&mut self, world: &mut World, context: &mut UpdateContext) {
+        for (_, actor) in world.actors.iter_mut() {
+            actor.update(context);
+        }
+    }
+}
+
+/// Hız bileşeni olan entity'lerin konumlarını günceller.
+pub struct MovementSystem;
+
+impl System for MovementSystem {
+    fn run(&mut self, world: &mut World, context: &mut UpdateContext) {
+        let delta_time = context.delta_time();
+        for (entity, velocity) in world.velocities.iter() {
+            if let Some(position) = world.positions.get_mut(entity) {
+                position.x += velocity.x * delta_time;
+                position.y += velocity.y * delta_time;
+            }
+        }
+    }
+}
+
+/// Sağlığı tükenen entity'leri dünyadan kaldırır.
+pub struct HealthSystem;
+
+impl System for HealthSystem {
+    fn run(&mut self, world: &mut World, _context: &mut UpdateContext) {
+        let dead: Vec<Entity> = world
+            .healths
+            .iter()
+            .filter(|(_, health)| health.is_dead())
+            .map(|(entity, _)| entity)
+            .collect();
+        for entity in dead {
+            world.despawn(entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::Renderer;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct Dummy {
+        updates: Rc<Cell<u32>>,
+    }
+
+    impl Actor for Dummy {
+        fn id(&self) -> u32 {
+            42
+        }
+        fn draw(&self, _renderer: &mut dyn Renderer) {}
+        fn update(&mut self, _context: &mut UpdateContext) {
+            self.updates.set(self.updates.get() + 1);
+        }
+    }
+
+    #[test]
+    fn should_move_entities_with_velocity_test() {
+        let mut world = World::new();
+        let moving = world.spawn();
+        world.positions.insert(moving, Position { x: 1.0, y: 1.0 });
+        world
+            .velocities
+            .insert(moving, Velocity { x: 10.0, y: -4.0 });
+        let still = world.spawn();
+        world.positions.insert(still, Position { x: 5.0, y: 5.0 });
+
+        MovementSystem.run(&mut world, &mut UpdateContext::new(0.5));
+
+        assert_eq!(
+            world.positions.get(moving),
+            Some(&Position { x: 6.0, y: -1.0 })
+        );
+        assert_eq!(
+            world.positions.get(still),
+            Some(&Position { x: 5.0, y: 5.0 })
+        );
+    }
+
+    #[test]
+    fn should_despawn_dead_entities_with_components_test() {
+        let mut world = World::new();
+        let goomba = world.spawn();
+        world.positions.insert(goomba, Position::default());
+        world.healths.insert(goomba, Health { current: 0, max: 3 });
+
+        HealthSystem.run(&mut world, &mut UpdateContext::new(0.1));
+
+        assert!(!world.is_alive(goomba));
+        assert!(world.positions.is_empty());
+        assert!(world.healths.is_empty());
+    }
+
+    #[test]
+    fn should_update_actors_through_adapter_test() {
+        let mut world = World::new();
+        let updates = Rc::new(Cell::new(0));
+        let entity = world.spawn_actor(Box::new(Dummy {
+            updates: Rc::clone(&updates),
+        }));
+
+        ActorSystem.run(&mut world, &mut UpdateContext::new(0.1));
+        ActorSystem.run(&mut world, &mut UpdateContext::new(0.1));
+
+        assert_eq!(updates.get(), 2);
+        assert!(world.is_alive(entity));
+        assert!(world.find_actor_mut(42).is_some());
+        assert!(world.find_actor_mut(7).is_none());
+    }
+}
@@ -0,0 +1,313 @@
+use crate::ecs::Position;
+use crate::framework::{EngineRequest, Game};
+use crate::input::Action;
+use crate::render::{CELL_WIDTH, Renderer};
+use std::collections::VecDeque;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+/// Oyun döngüsüne ait ayarlar.
+///
+/// `fixed_delta` her update çağrısında aktörlere geçilen sabit zaman aralığıdır.
+/// Çizim işlemleri ise her frame'de bir kez ve bu değerden bağımsız olarak yapılır.
+#[derive(Debug, Clone, Copy)]
+pub struct EngineConfig {
+    pub fixed_delta: Duration,
+    pub target_fps: u32,
+    pub max_updates_per_frame: u32,
+}
+
+impl EngineConfig {
+    pub fn new(updates_per_second: u32, target_fps: u32) -> Self {
+        EngineConfig {
+            fixed_delta: Duration::from_secs_f64(1.0 / updates_per_second.max(1) as f64),
+            target_fps: target_fps.max(1),
+            ..Default::default()
+        }
+    }
+    fn frame_budget(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.target_fps as f64)
+    }
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        EngineConfig {
+            fixed_delta: Duration::from_secs_f64(1.0 / 60.0),
+            target_fps: 60,
+            // Yavaş bir frame sonrasında döngünün update'leri yetiştirmeye çalışırken
+            // kilitlenmemesi için (spiral of death) üst sınır
+            max_updates_per_frame: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineState {
+    Running,
+    Paused,
+    Stopped,
+}
+
+const FRAME_HISTORY: usize = 60;
+
+/// Frame sürelerini ve FPS değerini takip eden istatistik nesnesi.
+#[derive(Debug, Default)]
+pub struct FrameStats {
+    frame_count: u64,
+    update_count: u64,
+    frame_times: VecDeque<Duration>,
+}
+
+impl FrameStats {
+    fn record(&mut self, frame_time: Duration, updates: u32) {
+        self.frame_count += 1;
+        self.update_count += updates as u64;
+        if self.frame_times.len() == FRAME_HISTORY {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(frame_time);
+    }
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+    pub fn update_count(&self) -> u64 {
+        self.update_count
+    }
+    pub fn last_frame_time(&self) -> Duration {
+        self.frame_times.back().copied().unwrap_or_default()
+    }
+    pub fn average_frame_time(&self) -> Duration {
+        if self.frame_times.is_empty() {
+            return Duration::ZERO;
+        }
+        self.frame_times.iter().sum::<Duration>() / self.frame_times.len() as u32
+    }
+    pub fn fps(&self) -> f32 {
+        let average = self.average_frame_time().as_secs_f32();
+        if average > 0.0 { 1.0 / average } else { 0.0 }
+    }
+}
+
+pub struct GameEngine {
+    config: EngineConfig,
+    state: EngineState,
+    accumulator: Duration,
+    stats: FrameStats,
+    renderer: Option<Box<dyn Renderer>>,
+}
+
+impl GameEngine {
+    pub fn new(config: EngineConfig) -> Self {
+        GameEngine {
+            config,
+            state: EngineState::Running,
+            accumulator: Duration::ZERO,
+            stats: FrameStats::default(),
+            renderer: None,
+        }
+    }
+    /// Renderer verilmezse frame'ler çizilmeden işletilir.
+    pub fn set_renderer(&mut self, renderer: Box<dyn Renderer>) {
+        self.renderer = Some(renderer);
+    }
+
+    /// Oyun durdurulana kadar gerçek zamanlı olarak frame'leri işletir.
+    /// Döngü sonlandığında oyundaki bot'lar da kapatılır.
+    pub fn run(&mut self, game: &mut Game) {
+        let budget = self.config.frame_budget();
+        let mut last = Instant::now();
+        while self.state != EngineState::Stopped {
+            let frame_start = Instant::now();
+            self.frame(game, frame_start - last);
+            last = frame_start;
+
+            // Hedeflenen FPS değerini aşmamak için frame'in kalan süresi kadar beklenir
+            let spent = frame_start.elapsed();
+            if spent < budget {
+                sleep(budget - spent);
+            }
+        }
+        game.shutdown();
+        // Terminal renderer drop edildiğinde imleç geri getirilir
+        self.renderer = None;
+    }
+
+    /// Tek bir frame işletir. Geçen süre kadar sabit adımlı update yapılır ve ardından çizilir.
+    /// Gerçek saatten bağımsız olduğu için testlerde de doğrudan kullanılabilir.
+    pub fn frame(&mut self, game: &mut Game, elapsed: Duration) {
+        if self.state == EngineState::Stopped {
+            return;
+        }
+
+        // Duraklatma ve çıkış tuşları oyun duraklatılmış olsa bile motor tarafından ele alınır
+        let input = game.begin_frame(elapsed);
+        if input.is_pressed(Action::Quit) {
+            self.shutdown();
+            game.end_frame();
+            return;
+        }
+        if input.is_pressed(Action::Pause) {
+            match self.state {
+                EngineState::Paused => self.resume(),
+                _ => self.pause(),
+            }
+        }
+
+        let mut updates = 0;
+        if self.state == EngineState::Running {
+            self.accumulator += elapsed;
+            while self.accumulator >= self.config.fixed_delta {
+                if updates == self.config.max_updates_per_frame {
+                    // Yetişilemeyen süre çöpe atılır, oyun yavaşlar ama donmaz
+                    self.accumulator = Duration::ZERO;
+                    break;
+                }
+                self.accumulator -= self.config.fixed_delta;
+                updates += 1;
+                let requests = game.update(self.config.fixed_delta.as_secs_f32());
+                self.handle_requests(requests);
+                if self.state != EngineState::Running {
+                    break;
+                }
+            }
+        }
+
+        game.end_frame();
+        self.stats.record(elapsed, updates);
+        if let Some(renderer) = self.renderer.as_mut() {
+            renderer.begin_frame();
+            game.draw(renderer.as_mut());
+            let fps = format!("FPS {:>5.1}", self.stats.fps());
+            let right = Position {
+                x: game.size().width() - fps.len() as f32 * CELL_WIDTH,
+                y: 0.0,
+            };
+            renderer.draw_text(right, &fps);
+            renderer.present();
+        }
+    }
+
+    fn handle_requests(&mut self, requests: Vec<EngineRequest>) {
+        for request in requests {
+            match request {
+                EngineRequest::Pause => self.pause(),
+                EngineRequest::Resume => self.resume(),
+                EngineRequest::Shutdown => self.shutdown(),
+            }
+        }
+    }
+
+    pub fn pause(&mut self) {
+        if self.state == EngineState::Running {
+            self.state = EngineState::Paused;
+            self.accumulator = Duration::ZERO;
+        }
+    }
+    pub fn resume(&mut self) {
+        if self.state == EngineState::Paused {
+            self.state = EngineState::Running;
+        }
+    }
+    pub fn shutdown(&mut self) {
+        self.state = EngineState::Stopped;
+    }
+    pub fn state(&self) -> EngineState {
+        self.state
+    }
+    pub fn stats(&self) -> &FrameStats {
+        &self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::{Actor, UpdateContext};
+    use crate::input::{Key, ScriptedInput};
+    use crate::render::Renderer;
+
+    struct Counter {
+        updates: u32,
+        total_time: f32,
+        shutdown_after: u32,
+    }
+
+    impl Actor for Counter {
+        fn id(&self) -> u32 {
+            1
+        }
+        fn draw(&self, _renderer: &mut dyn Renderer) {}
+        fn update(&mut self, context: &mut UpdateContext) {
+            self.updates += 1;
+            self.total_time += context.delta_time();
+            if self.updates == self.shutdown_after {
+                context.request(EngineRequest::Shutdown);
+            }
+        }
+    }
+
+    fn create_game(shutdown_after: u32) -> Game {
+        let mut game = Game::new();
+        game.add_actor(Box::new(Counter {
+            updates: 0,
+            total_time: 0.0,
+            shutdown_after,
+        }));
+        game
+    }
+
+    #[test]
+    fn should_run_fixed_updates_for_elapsed_time_test() {
+        let mut game = create_game(u32::MAX);
+        let mut engine = GameEngine::new(EngineConfig::new(10, 60));
+        engine.frame(&mut game, Duration::from_millis(250));
+        engine.frame(&mut game, Duration::from_millis(50));
+        assert_eq!(engine.stats().frame_count(), 2);
+        assert_eq!(engine.stats().update_count(), 3);
+    }
+
+    #[test]
+    fn should_not_update_while_paused_test() {
+        let mut game = create_game(u32::MAX);
+        let mut engine = GameEngine::new(EngineConfig::new(10, 60));
+        engine.pause();
+        engine.frame(&mut game, Duration::from_secs(1));
+        assert_eq!(engine.state(), EngineState::Paused);
+        assert_eq!(engine.stats().update_count(), 0);
+
+        engine.resume();
+        engine.frame(&mut game, Duration::from_millis(100));
+        assert_eq!(engine.stats().update_count(), 1);
+    }
+
+    #[test]
+    fn should_stop_when_actor_requests_shutdown_test() {
+        let mut game = create_game(2);
+        let mut engine = GameEngine::new(EngineConfig::new(10, 60));
+        engine.frame(&mut game, Duration::from_millis(500));
+        assert_eq!(engine.state(), EngineState::Stopped);
+        assert_eq!(engine.stats().update_count(), 2);
+    }
+
+    #[test]
+    fn should_toggle_pause_and_quit_from_input_test() {
+        let mut game = create_game(u32::MAX);
+        game.set_input_source(Box::new(
+            ScriptedInput::new()
+                .press(0, Key::Char('p'))
+                .press(2, Key::Char('p'))
+                .press(3, Key::Char('q')),
+        ));
+        let mut engine = GameEngine::new(EngineConfig::new(10, 60));
+
+        engine.frame(&mut game, Duration::from_millis(100));
+        assert_eq!(engine.state(), EngineState::Paused);
+        engine.frame(&mut game, Duration::from_millis(100));
+        engine.frame(&mut game, Duration::from_millis(100));
+        assert_eq!(engine.state(), EngineState::Running);
+        assert_eq!(engine.stats().update_count(), 1);
+        engine.frame(&mut game, Duration::from_millis(100));
+        assert_eq!(engine.state(), EngineState::Stopped);
+    }
+}
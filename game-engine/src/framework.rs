@@ -1,82 +1,629 @@
-use std::sync::mpsc::Sender;
+use crate::ai::{BehaviourContext, BehaviourTree};
+use crate::ecs::Position;
+use crate::ecs::{ActorSystem, Entity, HealthSystem, MovementSystem, System, World};
+use crate::input::{InputSource, InputState, KeyBindings};
+use crate::message::{BotCommand, GameMessage};
+use crate::physics::{self, BoundsMode, Transform};
+use crate::random::Random;
+use crate::render::{CELL_HEIGHT, Renderer};
+use crate::replay::{Recording, Session, state_hash};
+use crate::scene::Properties;
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Bot thread'lerine durmaları gerektiğini bildirmek için kullanılan iptal nesnesi.
+///
+/// Klonlanan tüm nesneler aynı durumu paylaşır.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    state: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn cancel(&self) {
+        let (cancelled, signal) = &*self.state;
+        *cancelled.lock().unwrap() = true;
+        signal.notify_all();
+    }
+    pub fn is_cancelled(&self) -> bool {
+        *self.state.0.lock().unwrap()
+    }
+    /// En fazla `timeout` kadar bekler, iptal edilirse beklemeden çıkar.
+    /// Bekleme sonunda iptal edilmiş olup olunmadığını döner.
+    pub fn wait(&self, timeout: Duration) -> bool {
+        let (cancelled, signal) = &*self.state;
+        let guard = cancelled.lock().unwrap();
+        let (guard, _) = signal
+            .wait_timeout_while(guard, timeout, |cancelled| !*cancelled)
+            .unwrap();
+        *guard
+    }
+}
+
+/// Bot thread'ine geçilen bağlam. İptal durumunu ve oyuna komut gönderebilmeyi sağlar.
+#[derive(Clone)]
+pub struct BotContext {
+    bot: usize,
+    token: CancellationToken,
+    sender: Sender<GameMessage>,
+}
+
+impl BotContext {
+    pub fn new(bot: usize, sender: Sender<GameMessage>) -> Self {
+        BotContext {
+            bot,
+            token: CancellationToken::new(),
+            sender,
+        }
+    }
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+    pub fn wait(&self, timeout: Duration) -> bool {
+        self.token.wait(timeout)
+    }
+    /// Komutu oyuna gönderir. Oyun sonlanmışsa false döner.
+    pub fn send(&self, command: BotCommand) -> bool {
+        self.sender
+            .send(GameMessage::BotCommand {
+                bot: self.bot,
+                command,
+            })
+            .is_ok()
+    }
+}
+
+/// Çalışan bir bot'un durumu. Bot ya kendi thread'inde ya da oyunun thread'inde
+/// davranış ağacı ile çalışır.
+enum BotWorker {
+    Thread {
+        token: CancellationToken,
+        handle: JoinHandle<()>,
+    },
+    Tree(BehaviourTree),
+}
 
 pub struct BotContainer {
     is_active: bool,
-    bot: Box<dyn Bot>,
+    bot: Arc<dyn Bot>,
+    worker: Option<BotWorker>,
 }
 impl BotContainer {
     pub fn new(is_active: bool, bot: Box<dyn Bot>) -> Self {
-        BotContainer { is_active, bot }
+        BotContainer {
+            is_active,
+            bot: Arc::from(bot),
+            worker: None,
+        }
+    }
+    pub fn is_active(&self) -> bool {
+        self.is_active
+    }
+    pub fn bot(&self) -> &dyn Bot {
+        self.bot.as_ref()
+    }
+    /// Bot başlatılmış ve henüz sonlanmamışsa true döner.
+    pub fn is_running(&self) -> bool {
+        match &self.worker {
+            Some(BotWorker::Thread { handle, .. }) => !handle.is_finished(),
+            Some(BotWorker::Tree(_)) => true,
+            None => false,
+        }
+    }
+    fn activate(&mut self, context: BotContext) {
+        self.is_active = true;
+        self.start(context);
+    }
+    fn deactivate(&mut self) {
+        self.is_active = false;
+        self.stop();
+    }
+    fn start(&mut self, context: BotContext) {
+        if self.worker.is_some() {
+            return;
+        }
+        // Ağaç her başlatmada yeniden oluşturulur, böylece yarım kalan işler devam ettirilmez
+        if let Some(tree) = self.bot.behaviour() {
+            self.worker = Some(BotWorker::Tree(tree));
+            return;
+        }
+        let token = context.token.clone();
+        let bot = Arc::clone(&self.bot);
+        let handle = thread::spawn(move || bot.apply(&context));
+        self.worker = Some(BotWorker::Thread { token, handle });
+    }
+    fn stop(&mut self) {
+        if let Some(BotWorker::Thread { token, handle }) = self.worker.take() {
+            token.cancel();
+            if handle.join().is_err() {
+                eprintln!("Bot thread'i hata ile sonlandı");
+            }
+        }
+    }
+    fn tree_mut(&mut self) -> Option<&mut BehaviourTree> {
+        match &mut self.worker {
+            Some(BotWorker::Tree(tree)) => Some(tree),
+            _ => None,
+        }
     }
 }
 
-pub trait Bot {
-    fn apply(&self) {
+impl Drop for BotContainer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Oyundaki yapay zeka davranışlarını temsil eder.
+///
+/// Bot'lar iki şekilde çalışabilir. `behaviour` bir davranış ağacı dönerse ağaç oyunun
+/// thread'inde her update'te çalıştırılır ve dünyayı inceleyerek komut verebilir. Aksi halde
+/// `apply` fonksiyonu bot'a ait thread içerisinde çalıştırılır. Uzun süreli çalışan bot'lar
+/// bağlam iptal edildiğinde döngülerinden çıkmalıdır.
+pub trait Bot: Send + Sync {
+    fn apply(&self, _context: &BotContext) {
         println!("Default AI movements");
     }
+    /// Bot her başlatıldığında çalıştırılacak davranış ağacını oluşturur
+    fn behaviour(&self) -> Option<BehaviourTree> {
+        None
+    }
+    /// Sahne ve kayıt dosyalarında bot'u tanımlayan tür ismi
+    fn kind(&self) -> &'static str {
+        "bot"
+    }
+    /// Bot'u yeniden oluşturabilmek için gereken özellikleri kaydeder
+    fn save(&self, _properties: &mut Properties) {}
 }
 
-#[allow(dead_code)]
 pub trait Actor {
-    fn draw(&self);
-    fn update(&mut self);
+    fn id(&self) -> u32;
+    fn draw(&self, renderer: &mut dyn Renderer);
+    fn update(&mut self, context: &mut UpdateContext);
+    /// Aktörün türünü belirten isim. Örneğin çarpışmalarda karşı tarafı tanımak için kullanılır.
+    fn kind(&self) -> &'static str {
+        "actor"
+    }
+    fn strength(&self) -> u8 {
+        0
+    }
+    /// Dünyada konumu olmayan aktörler çarpışma ve sınır kontrollerine dahil edilmez
+    fn transform(&self) -> Option<&Transform> {
+        None
+    }
+    fn transform_mut(&mut self) -> Option<&mut Transform> {
+        None
+    }
+    /// Sınır kutusu başka bir aktör ile çakıştığında çağrılır
+    fn on_collision(&mut self, _other: &dyn Actor) {}
+    /// Bot'lardan gelen komutlar bu fonksiyon ile aktöre iletilir
+    fn on_command(&mut self, _command: &BotCommand) {}
+    /// Aktörü yeniden oluşturabilmek için gereken özellikleri kaydeder.
+    /// Kaydedilen özellikler sahne kayıt defterindeki (registry) oluşturucuya geri verilir.
+    fn save(&self, _properties: &mut Properties) {}
+}
+
+/// Aktörlerin oyun motorundan talep edebileceği işlemler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineRequest {
+    Pause,
+    Resume,
+    Shutdown,
+}
+
+/// Her update çağrısında aktörlere geçilen bağlam nesnesi.
+pub struct UpdateContext {
+    delta_time: f32,
+    input: InputState,
+    random: Random,
+    requests: Vec<EngineRequest>,
+    spawns: Vec<Box<dyn Actor>>,
+    destroys: Vec<u32>,
+}
+
+impl UpdateContext {
+    pub fn new(delta_time: f32) -> Self {
+        Self::with_input(delta_time, InputState::default())
+    }
+    pub fn with_input(delta_time: f32, input: InputState) -> Self {
+        UpdateContext {
+            delta_time,
+            input,
+            random: Random::default(),
+            requests: Vec::new(),
+            spawns: Vec::new(),
+            destroys: Vec::new(),
+        }
+    }
+    /// Son update'ten bu yana geçen süre (saniye)
+    pub fn delta_time(&self) -> f32 {
+        self.delta_time
+    }
+    /// Bu frame'de basılan tuşlar
+    pub fn input(&self) -> &InputState {
+        &self.input
+    }
+    /// Oyunun rastgele sayı üreteci. Kayıtların tekrar oynatılabilmesi için aktörler
+    /// başka bir rastgelelik kaynağı kullanmamalıdır.
+    pub fn random(&mut self) -> &mut Random {
+        &mut self.random
+    }
+    pub fn request(&mut self, request: EngineRequest) {
+        self.requests.push(request);
+    }
+    /// Aktörün dünyaya eklenmesini ister. Ekleme frame sonunda yapılır.
+    pub fn spawn(&mut self, actor: Box<dyn Actor>) {
+        self.spawns.push(actor);
+    }
+    /// Aktörün dünyadan kaldırılmasını ister. Kaldırma frame sonunda yapılır.
+    pub fn destroy(&mut self, actor_id: u32) {
+        self.destroys.push(actor_id);
+    }
 }
 
 // #[derive(Default)]
-#[allow(dead_code)]
 pub struct Game {
     // width: f32,
     // height: f32,
     size: Size,
-    actors: Vec<Box<dyn Actor>>,
+    bounds_mode: BoundsMode,
+    world: World,
+    systems: Vec<Box<dyn System>>,
     bots_container: Vec<BotContainer>,
-    sender: Sender<String>,
+    sender: Sender<GameMessage>,
+    receiver: Receiver<GameMessage>,
+    input_source: Option<Box<dyn InputSource>>,
+    key_bindings: KeyBindings,
+    input: InputState,
+    score: i64,
+    last_event: Option<String>,
+    pending_spawns: Vec<(Entity, Box<dyn Actor>)>,
+    pending_despawns: Vec<Entity>,
+    random: Random,
+    session: Session,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Game {
-    pub fn new(sender: Sender<String>) -> Self {
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
         Game {
-            actors: Vec::new(),
+            world: World::new(),
+            systems: vec![
+                Box::new(ActorSystem),
+                Box::new(MovementSystem),
+                Box::new(HealthSystem),
+            ],
             size: Size::default(),
+            bounds_mode: BoundsMode::default(),
             bots_container: Vec::new(),
             sender,
+            receiver,
+            input_source: None,
+            key_bindings: KeyBindings::default(),
+            input: InputState::default(),
+            score: 0,
+            last_event: None,
+            pending_spawns: Vec::new(),
+            pending_despawns: Vec::new(),
+            random: Random::default(),
+            session: Session::Live,
+        }
+    }
+    /// Oyuna dışarıdan mesaj göndermek için kanalın gönderici tarafını döner.
+    pub fn sender(&self) -> Sender<GameMessage> {
+        self.sender.clone()
+    }
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+    pub fn set_score(&mut self, score: i64) {
+        self.score = score;
+    }
+    /// Ekranın altında gösterilen son olay. Örneğin aktör ekleme ve kaldırma bilgisi.
+    pub fn last_event(&self) -> Option<&str> {
+        self.last_event.as_deref()
+    }
+    /// Aktörü dünyaya bir entity olarak hemen ekler. Oyun çalışırken ekleme yapmak için
+    /// `spawn_actor` kullanılmalıdır.
+    pub fn add_actor(&mut self, actor: Box<dyn Actor>) -> Entity {
+        let id = actor.id();
+        let entity = self.world.spawn_actor(actor);
+        // Alıcı taraf Game içerisinde tutulduğu için gönderim başarısız olamaz
+        let _ = self.sender.send(GameMessage::ActorSpawned { id });
+        entity
+    }
+    /// Aktörün frame sonunda dünyaya eklenmesini sağlar. Dönen entity aktör eklenmeden
+    /// önce de etiketlemek veya kaldırmak için kullanılabilir.
+    pub fn spawn_actor(&mut self, actor: Box<dyn Actor>) -> Entity {
+        let entity = self.world.reserve();
+        self.pending_spawns.push((entity, actor));
+        entity
+    }
+    /// Entity'nin frame sonunda dünyadan kaldırılmasını sağlar.
+    pub fn despawn(&mut self, entity: Entity) {
+        self.pending_despawns.push(entity);
+    }
+    /// Bekleyen ekleme ve kaldırma işlemlerini uygular. Oyun motoru tarafından her frame
+    /// sonunda çağrılır. Eklenen ve kaldırılan aktörler için kanala mesaj gönderilir.
+    pub fn apply_pending(&mut self) {
+        for (entity, actor) in std::mem::take(&mut self.pending_spawns) {
+            let id = actor.id();
+            // Etiketler ekleme öncesinde verilmiş olabilir, aktör türü bunlara eklenir
+            self.world.insert_actor(entity, actor);
+            let _ = self.sender.send(GameMessage::ActorSpawned { id });
         }
+        for entity in std::mem::take(&mut self.pending_despawns) {
+            let id = self.world.actor(entity).map(|actor| actor.id());
+            if self.world.despawn(entity)
+                && let Some(id) = id
+            {
+                let _ = self.sender.send(GameMessage::ActorDestroyed { id });
+            }
+        }
+    }
+    /// Sistemler eklendikleri sırada, varsayılan sistemlerden sonra çalıştırılır.
+    pub fn add_system(&mut self, system: Box<dyn System>) {
+        self.systems.push(system);
+    }
+    pub fn size(&self) -> &Size {
+        &self.size
+    }
+    pub fn set_size(&mut self, size: Size) {
+        self.size = size;
+    }
+    pub fn set_bounds_mode(&mut self, mode: BoundsMode) {
+        self.bounds_mode = mode;
+    }
+    pub fn set_input_source(&mut self, source: Box<dyn InputSource>) {
+        self.input_source = Some(source);
+    }
+    pub fn key_bindings_mut(&mut self) -> &mut KeyBindings {
+        &mut self.key_bindings
+    }
+    /// Rastgele sayı üretecini verilen tohum ile yeniden başlatır.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.random = Random::new(seed);
     }
-    pub fn add_actor(&mut self, actor: Box<dyn Actor>) {
-        self.actors.push(actor);
+    /// Oturumu kaydetmeye başlar. Rastgele sayı üreteci verilen tohum ile yeniden başlatılır.
+    pub fn start_recording(&mut self, seed: u64) {
+        self.set_seed(seed);
+        self.session = Session::Recording(Recording::new(seed));
     }
-    pub fn draw(&self) {
-        for actor in &self.actors {
-            actor.draw();
+    /// Kaydı sonlandırır ve kaydedilen oturumu döner. Kayıt yapılmıyorsa `None` döner.
+    pub fn stop_recording(&mut self) -> Option<Recording> {
+        match std::mem::replace(&mut self.session, Session::Live) {
+            Session::Recording(recording) => Some(recording),
+            session => {
+                self.session = session;
+                None
+            }
         }
     }
-    pub fn add_bot(&mut self, bot: Box<dyn Bot>) {
-        self.bots_container.push(BotContainer::new(true, bot));
+    pub(crate) fn start_replay(&mut self, recording: Recording) {
+        self.set_seed(recording.seed);
+        self.session = Session::Replaying {
+            recording,
+            frame: 0,
+            update: 0,
+        };
+    }
+    pub(crate) fn stop_replay(&mut self) {
+        if self.session.is_replaying() {
+            self.session = Session::Live;
+        }
     }
-    pub fn update(&mut self) {
-        for actor in &mut self.actors {
-            actor.update();
+    /// Oyun motoru tarafından her frame başında çağrılır. Frame'in girdisini hazırlar.
+    ///
+    /// Kayıt oynatılıyorsa tuşlar girdi kaynağı yerine kayıttan okunur.
+    pub fn begin_frame(&mut self, elapsed: Duration) -> &InputState {
+        if self.session.is_replaying() {
+            self.input = InputState::from_keys(self.session.replayed_keys(), &self.key_bindings);
+        } else {
+            self.poll_input();
         }
+        self.session.begin_frame(elapsed, self.input.keys());
+        &self.input
+    }
+    /// Oyun motoru tarafından her frame sonunda çağrılır.
+    pub fn end_frame(&mut self) {
+        self.apply_pending();
+        let state = self.session.is_recording().then(|| state_hash(self));
+        self.session.end_frame(state);
+    }
+    /// Girdi kaynağındaki tuşları okur. Frame başında bir kez çağrılır ve
+    /// o frame'deki tüm update'ler aynı girdiyi görür.
+    pub fn poll_input(&mut self) -> &InputState {
+        let keys = match &mut self.input_source {
+            Some(source) => source.poll(),
+            None => Vec::new(),
+        };
+        self.input = InputState::from_keys(keys, &self.key_bindings);
+        &self.input
+    }
+    pub fn world(&self) -> &World {
+        &self.world
     }
-    pub fn apply(&self) {
-        for container in &self.bots_container {
-            self.sender
-                .send("A simple message...".to_string())
-                .expect("Error in sending message into channel");
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+    /// Dünyayı ve skor bilgisini çizer. Frame'in başlatılması ve ekrana yansıtılması
+    /// oyun motorunun sorumluluğundadır.
+    pub fn draw(&self, renderer: &mut dyn Renderer) {
+        for (entity, sprite) in self.world.sprites.iter() {
+            if let Some(position) = self.world.positions.get(entity) {
+                renderer.draw_glyph(*position, sprite.glyph);
+            }
+        }
+        for (_, actor) in self.world.actors.iter() {
+            actor.draw(renderer);
+        }
+        renderer.draw_text(Position::default(), &format!("Score {}", self.score));
+        if let Some(event) = &self.last_event {
+            let bottom = Position {
+                x: 0.0,
+                y: self.size.height() - CELL_HEIGHT,
+            };
+            renderer.draw_text(bottom, event);
+        }
+    }
+    /// Bot'u ekler ve sonradan erişebilmek için sıra numarasını döner.
+    pub fn add_bot(&mut self, bot: Box<dyn Bot>) -> usize {
+        self.add_bot_with_state(bot, true)
+    }
+    /// Bot'u verilen aktiflik durumu ile ekler. Pasif bot'lar `apply` çağrısında başlatılmaz.
+    pub fn add_bot_with_state(&mut self, bot: Box<dyn Bot>, is_active: bool) -> usize {
+        self.bots_container.push(BotContainer::new(is_active, bot));
+        self.bots_container.len() - 1
+    }
+    /// Bot'u çalışma zamanında aktif veya pasif hale getirir. Bot bulunamazsa false döner.
+    pub fn set_bot_active(&mut self, index: usize, is_active: bool) -> bool {
+        match self.bots_container.get_mut(index) {
+            Some(container) if is_active => {
+                container.activate(BotContext::new(index, self.sender.clone()))
+            }
+            Some(container) => container.deactivate(),
+            None => return false,
+        }
+        true
+    }
+    pub fn bots(&self) -> &[BotContainer] {
+        &self.bots_container
+    }
+    pub fn update(&mut self, delta_time: f32) -> Vec<EngineRequest> {
+        let mut context = UpdateContext::with_input(delta_time, self.input.clone());
+        context.random = std::mem::take(&mut self.random);
+        self.session.begin_update();
+        if self.session.is_replaying() {
+            self.replay_messages(&mut context);
+        } else {
+            self.process_messages(&mut context);
+            self.tick_bots(delta_time);
+        }
+        for system in &mut self.systems {
+            system.run(&mut self.world, &mut context);
+        }
+        self.random = std::mem::take(&mut context.random);
+        physics::keep_in_bounds(&mut self.world, &self.size, self.bounds_mode);
+        let collisions = physics::find_collisions(&self.world);
+        physics::notify_collisions(&mut self.world, &collisions);
+
+        for actor in context.spawns {
+            self.spawn_actor(actor);
+        }
+        for actor_id in context.destroys {
+            match self.world.entity_of(actor_id) {
+                Some(entity) => self.despawn(entity),
+                None => eprintln!("Unknown actor {actor_id} could not be destroyed"),
+            }
+        }
+        context.requests
+    }
+    /// Davranış ağacı ile çalışan aktif bot'ları çalıştırır ve verdikleri komutları uygular.
+    fn tick_bots(&mut self, delta_time: f32) {
+        for (index, container) in self.bots_container.iter_mut().enumerate() {
+            let Some(tree) = container.tree_mut() else {
+                continue;
+            };
+            let mut context = BehaviourContext::new(index, &self.world, delta_time);
+            tree.tick(&mut context);
+            for command in context.into_commands() {
+                self.session.record(&GameMessage::BotCommand {
+                    bot: index,
+                    command: command.clone(),
+                });
+                deliver_command(&mut self.world, index, &command);
+            }
+        }
+    }
+    /// Kanalda bekleyen mesajları bloklamadan okur ve işler.
+    fn process_messages(&mut self, context: &mut UpdateContext) {
+        while let Ok(message) = self.receiver.try_recv() {
+            self.session.record(&message);
+            self.handle_message(message, context);
+        }
+    }
+    /// Kanaldaki mesajlar yerine kayıttaki mesajları işler. Bot'ların komutları da bunlara dahildir.
+    fn replay_messages(&mut self, context: &mut UpdateContext) {
+        // Sahne yüklenirken gönderilen mesajlar kayıtta zaten yer aldığı için atılır
+        while self.receiver.try_recv().is_ok() {}
+        for message in self.session.next_replayed_update() {
+            self.handle_message(message, context);
+        }
+    }
+    fn handle_message(&mut self, message: GameMessage, context: &mut UpdateContext) {
+        match message {
+            GameMessage::ActorSpawned { id } => {
+                self.last_event = Some(format!("Actor {id} spawned"))
+            }
+            GameMessage::ActorDestroyed { id } => {
+                self.last_event = Some(format!("Actor {id} destroyed"))
+            }
+            GameMessage::BotCommand { bot, command } => {
+                deliver_command(&mut self.world, bot, &command)
+            }
+            GameMessage::ScoreChanged { delta } => self.score += delta as i64,
+            GameMessage::Shutdown => context.request(EngineRequest::Shutdown),
+        }
+    }
+    /// Aktif bot'ları başlatır. Kayıt oynatılırken bot'lar başlatılmaz.
+    pub fn apply(&mut self) {
+        if self.session.is_replaying() {
+            return;
+        }
+        for (index, container) in self.bots_container.iter_mut().enumerate() {
             if container.is_active {
-                container.bot.apply();
+                container.start(BotContext::new(index, self.sender.clone()));
             }
         }
     }
+    /// Tüm bot thread'lerine iptal sinyali gönderir ve sonlanmalarını bekler.
+    pub fn shutdown(&mut self) {
+        for container in &mut self.bots_container {
+            container.stop();
+        }
+    }
+}
+
+fn deliver_command(world: &mut World, bot: usize, command: &BotCommand) {
+    match world.find_actor_mut(command.target()) {
+        Some(actor) => actor.on_command(command),
+        None => eprintln!("Bot {bot} sent a command to unknown actor: {command:?}"),
+    }
 }
 
 // #[derive(Default)]
-#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Size {
     width: f32,
     height: f32,
 }
 
+impl Size {
+    pub fn new(width: f32, height: f32) -> Self {
+        Size { width, height }
+    }
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+}
+
 impl Default for Size {
     fn default() -> Self {
         Size {
@@ -85,3 +632,204 @@ impl Default for Size {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{Action, Sequence, Status, Wait};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct Ticker {
+        ticks: Arc<AtomicU32>,
+    }
+
+    impl Bot for Ticker {
+        fn apply(&self, context: &BotContext) {
+            while !context.is_cancelled() {
+                self.ticks.fetch_add(1, Ordering::SeqCst);
+                context.wait(Duration::from_millis(10));
+            }
+        }
+    }
+
+    struct Booster;
+
+    impl Bot for Booster {
+        fn apply(&self, context: &BotContext) {
+            context.send(BotCommand::ChangeStrength {
+                actor_id: 7,
+                amount: 3,
+            });
+        }
+    }
+
+    struct Nudger;
+
+    impl Bot for Nudger {
+        fn behaviour(&self) -> Option<BehaviourTree> {
+            Some(BehaviourTree::new(Box::new(Sequence::new(vec![
+                Box::new(Wait::new(0.2)),
+                Box::new(Action::new(|context: &mut BehaviourContext| {
+                    context.send(BotCommand::ChangeStrength {
+                        actor_id: 7,
+                        amount: 1,
+                    });
+                    Status::Success
+                })),
+            ]))))
+        }
+    }
+
+    // İlk update'te yerine bir çocuk aktör bırakıp kendini yok eder
+    struct Splitter {
+        id: u32,
+    }
+
+    impl Actor for Splitter {
+        fn id(&self) -> u32 {
+            self.id
+        }
+        fn kind(&self) -> &'static str {
+            "splitter"
+        }
+        fn draw(&self, _renderer: &mut dyn Renderer) {}
+        fn update(&mut self, context: &mut UpdateContext) {
+            if self.id == 1 {
+                context.spawn(Box::new(Splitter { id: 2 }));
+                context.destroy(self.id);
+            }
+        }
+    }
+
+    struct Target {
+        strength: Arc<Mutex<i16>>,
+    }
+
+    impl Actor for Target {
+        fn id(&self) -> u32 {
+            7
+        }
+        fn draw(&self, _renderer: &mut dyn Renderer) {}
+        fn update(&mut self, _context: &mut UpdateContext) {}
+        fn on_command(&mut self, command: &BotCommand) {
+            if let BotCommand::ChangeStrength { amount, .. } = command {
+                *self.strength.lock().unwrap() += amount;
+            }
+        }
+    }
+
+    #[test]
+    fn should_cancel_waiting_token_test() {
+        let token = CancellationToken::new();
+        let waiter = token.clone();
+        let handle = thread::spawn(move || waiter.wait(Duration::from_secs(30)));
+        token.cancel();
+        assert!(handle.join().unwrap());
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn should_start_toggle_and_join_bots_test() {
+        let mut game = Game::new();
+        let ticks = Arc::new(AtomicU32::new(0));
+        let index = game.add_bot(Box::new(Ticker {
+            ticks: Arc::clone(&ticks),
+        }));
+
+        game.apply();
+        assert!(game.bots()[index].is_running());
+
+        assert!(game.set_bot_active(index, false));
+        assert!(!game.bots()[index].is_active());
+        assert!(!game.bots()[index].is_running());
+        let stopped_at = ticks.load(Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(30));
+        assert_eq!(ticks.load(Ordering::SeqCst), stopped_at);
+
+        assert!(game.set_bot_active(index, true));
+        assert!(game.bots()[index].is_running());
+        assert!(!game.set_bot_active(index + 1, true));
+
+        game.shutdown();
+        assert!(!game.bots()[index].is_running());
+    }
+
+    #[test]
+    fn should_deliver_bot_commands_and_messages_on_update_test() {
+        let strength = Arc::new(Mutex::new(0));
+        let mut game = Game::new();
+        game.add_actor(Box::new(Target {
+            strength: Arc::clone(&strength),
+        }));
+        game.add_bot(Box::new(Booster));
+        game.apply();
+        game.shutdown();
+
+        let sender = game.sender();
+        sender.send(GameMessage::ScoreChanged { delta: 5 }).unwrap();
+        sender.send(GameMessage::Shutdown).unwrap();
+
+        let requests = game.update(0.1);
+        assert_eq!(requests, vec![EngineRequest::Shutdown]);
+        assert_eq!(game.score(), 5);
+        assert_eq!(*strength.lock().unwrap(), 3);
+    }
+
+    #[test]
+    fn should_tick_behaviour_tree_bots_on_update_test() {
+        let strength = Arc::new(Mutex::new(0));
+        let mut game = Game::new();
+        game.add_actor(Box::new(Target {
+            strength: Arc::clone(&strength),
+        }));
+        let index = game.add_bot(Box::new(Nudger));
+
+        // Başlatılmayan bot'un ağacı çalıştırılmaz
+        game.update(0.1);
+        game.update(0.1);
+        assert_eq!(*strength.lock().unwrap(), 0);
+
+        game.apply();
+        assert!(game.bots()[index].is_running());
+        for _ in 0..4 {
+            game.update(0.1);
+        }
+        assert_eq!(*strength.lock().unwrap(), 2);
+
+        game.set_bot_active(index, false);
+        game.update(0.2);
+        assert_eq!(*strength.lock().unwrap(), 2);
+    }
+
+    #[test]
+    fn should_apply_spawns_and_despawns_between_frames_test() {
+        let mut game = Game::new();
+        let first = game.add_actor(Box::new(Splitter { id: 1 }));
+        let world = game.world();
+        assert_eq!(world.tagged("splitter").collect::<Vec<_>>(), vec![first]);
+
+        game.update(0.1);
+        assert!(game.world().is_alive(first));
+        assert_eq!(game.world().actors.len(), 1);
+
+        game.end_frame();
+        assert!(!game.world().is_alive(first));
+        let second = game.world().entity_of(2).unwrap();
+        assert!(game.world().has_tag(second, "splitter"));
+
+        game.update(0.1);
+        assert_eq!(game.last_event(), Some("Actor 1 destroyed"));
+
+        let third = game.spawn_actor(Box::new(Splitter { id: 3 }));
+        game.world_mut().tag(third, "boss");
+        game.despawn(second);
+        game.end_frame();
+        assert_eq!(game.world().tagged("boss").collect::<Vec<_>>(), vec![third]);
+        assert_eq!(
+            game.world().tagged("splitter").collect::<Vec<_>>(),
+            vec![third]
+        );
+        game.update(0.1);
+        assert_eq!(game.last_event(), Some("Actor 2 destroyed"));
+    }
+}
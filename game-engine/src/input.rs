@@ -0,0 +1,279 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{self, IsTerminal, Read};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{Receiver, channel};
+use std::thread;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Key {
+    Char(char),
+    Ctrl(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+    Escape,
+}
+
+/// Tuşların oyun içerisindeki karşılıkları.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    Pause,
+    Quit,
+}
+
+/// Tuş ve aksiyon eşleştirmeleri.
+#[derive(Debug, Clone)]
+pub struct KeyBindings {
+    bindings: HashMap<Key, Action>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let mut bindings = KeyBindings::empty();
+        bindings.bind(Key::Left, Action::MoveLeft);
+        bindings.bind(Key::Char('a'), Action::MoveLeft);
+        bindings.bind(Key::Right, Action::MoveRight);
+        bindings.bind(Key::Char('d'), Action::MoveRight);
+        bindings.bind(Key::Up, Action::MoveUp);
+        bindings.bind(Key::Char('w'), Action::MoveUp);
+        bindings.bind(Key::Down, Action::MoveDown);
+        bindings.bind(Key::Char('s'), Action::MoveDown);
+        bindings.bind(Key::Char('p'), Action::Pause);
+        bindings.bind(Key::Char('q'), Action::Quit);
+        bindings.bind(Key::Escape, Action::Quit);
+        bindings.bind(Key::Ctrl('c'), Action::Quit);
+        bindings
+    }
+}
+
+impl KeyBindings {
+    pub fn empty() -> Self {
+        KeyBindings {
+            bindings: HashMap::new(),
+        }
+    }
+    /// Tuşu aksiyona bağlar. Tuş daha önce başka bir aksiyona bağlıysa bu eşleşme ezilir.
+    pub fn bind(&mut self, key: Key, action: Action) {
+        self.bindings.insert(key, action);
+    }
+    pub fn unbind(&mut self, key: Key) {
+        self.bindings.remove(&key);
+    }
+    pub fn action(&self, key: Key) -> Option<Action> {
+        self.bindings.get(&key).copied()
+    }
+}
+
+/// Frame içerisinde basılan tuşlar ve bunların karşılığı olan aksiyonlar.
+///
+/// Terminal tuşun bırakıldığı bilgisini vermediği için bir aksiyon sadece tuşun okunduğu
+/// frame'de aktif kabul edilir. Basılı tutulan tuşlar terminalin tekrar hızında gelir.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InputState {
+    keys: Vec<Key>,
+    actions: HashSet<Action>,
+}
+
+impl InputState {
+    pub fn from_keys(keys: Vec<Key>, bindings: &KeyBindings) -> Self {
+        let actions = keys
+            .iter()
+            .filter_map(|key| bindings.action(*key))
+            .collect();
+        InputState { keys, actions }
+    }
+    pub fn is_pressed(&self, action: Action) -> bool {
+        self.actions.contains(&action)
+    }
+    pub fn keys(&self) -> &[Key] {
+        &self.keys
+    }
+}
+
+/// Tuş bilgilerinin okunduğu kaynak. Her frame'de bir kez sorgulanır.
+pub trait InputSource {
+    fn poll(&mut self) -> Vec<Key>;
+}
+
+/// Önceden belirlenen frame'lerde tuş basımı üreten kaynak. Testlerde kullanılır.
+#[derive(Debug, Default)]
+pub struct ScriptedInput {
+    frame: u64,
+    script: BTreeMap<u64, Vec<Key>>,
+}
+
+impl ScriptedInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Verilen frame'de tuşun basılmasını sağlar. İlk frame 0'dır.
+    pub fn press(mut self, frame: u64, key: Key) -> Self {
+        self.script.entry(frame).or_default().push(key);
+        self
+    }
+    /// Tuşun `from` dahil `to` hariç aralıktaki tüm frame'lerde basılı olmasını sağlar.
+    pub fn hold(mut self, from: u64, to: u64, key: Key) -> Self {
+        for frame in from..to {
+            self = self.press(frame, key);
+        }
+        self
+    }
+}
+
+impl InputSource for ScriptedInput {
+    fn poll(&mut self) -> Vec<Key> {
+        let keys = self.script.remove(&self.frame).unwrap_or_default();
+        self.frame += 1;
+        keys
+    }
+}
+
+/// Terminali raw moda alarak tuşları ayrı bir thread'de okuyan kaynak.
+///
+/// Nesne drop edildiğinde terminalin önceki ayarları geri yüklenir. Okuma yapan thread
+/// stdin üzerinde bloklandığı için sonlandırılamaz, program kapanırken onunla birlikte kapanır.
+pub struct TerminalInput {
+    receiver: Receiver<Key>,
+    saved_settings: String,
+}
+
+impl TerminalInput {
+    pub fn new() -> io::Result<Self> {
+        if !io::stdin().is_terminal() {
+            return Err(io::Error::other("stdin is not a terminal"));
+        }
+        let saved_settings = stty(&["-g"])?;
+        // raw mod çıktı işlemeyi de kapattığı için println! satır başına dönmez, opost ile açılır
+        stty(&["raw", "-echo", "opost"])?;
+
+        let (sender, receiver) = channel();
+        thread::spawn(move || {
+            let mut stdin = io::stdin().lock();
+            let mut buffer = [0u8; 16];
+            while let Ok(count) = stdin.read(&mut buffer) {
+                if count == 0 {
+                    break;
+                }
+                for key in parse_keys(&buffer[..count]) {
+                    if sender.send(key).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(TerminalInput {
+            receiver,
+            saved_settings: saved_settings.trim().to_string(),
+        })
+    }
+}
+
+impl InputSource for TerminalInput {
+    fn poll(&mut self) -> Vec<Key> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+impl Drop for TerminalInput {
+    fn drop(&mut self) {
+        if stty(&[self.saved_settings.as_str()]).is_err() {
+            eprintln!("Terminal settings could not be restored. Try 'stty sane'");
+        }
+    }
+}
+
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Terminalden okunan byte'ları tuşlara çevirir. Ok tuşları ESC [ A..D dizileri olarak gelir.
+pub fn parse_keys(bytes: &[u8]) -> Vec<Key> {
+    let mut keys = Vec::new();
+    let mut index = 0;
+    while index < bytes.len() {
+        let key = match bytes[index] {
+            0x1b if bytes.get(index + 1) == Some(&b'[') && index + 2 < bytes.len() => {
+                index += 2;
+                match bytes[index] {
+                    b'A' => Some(Key::Up),
+                    b'B' => Some(Key::Down),
+                    b'C' => Some(Key::Right),
+                    b'D' => Some(Key::Left),
+                    _ => None,
+                }
+            }
+            0x1b => Some(Key::Escape),
+            b'\r' | b'\n' => Some(Key::Enter),
+            byte @ 1..=26 => Some(Key::Ctrl((b'a' + byte - 1) as char)),
+            byte if byte.is_ascii() => Some(Key::Char((byte as char).to_ascii_lowercase())),
+            _ => None,
+        };
+        keys.extend(key);
+        index += 1;
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_terminal_bytes_test() {
+        let keys = parse_keys(b"aD\x1b[A\x1b[D\x03\x1b\r");
+        assert_eq!(
+            keys,
+            vec![
+                Key::Char('a'),
+                Key::Char('d'),
+                Key::Up,
+                Key::Left,
+                Key::Ctrl('c'),
+                Key::Escape,
+                Key::Enter
+            ]
+        );
+    }
+
+    #[test]
+    fn should_map_keys_with_custom_bindings_test() {
+        let mut bindings = KeyBindings::default();
+        bindings.unbind(Key::Char('a'));
+        bindings.bind(Key::Char('j'), Action::MoveLeft);
+
+        let state = InputState::from_keys(vec![Key::Char('j'), Key::Char('x')], &bindings);
+        assert!(state.is_pressed(Action::MoveLeft));
+        assert!(!state.is_pressed(Action::MoveRight));
+        assert!(
+            !InputState::from_keys(vec![Key::Char('a')], &bindings).is_pressed(Action::MoveLeft)
+        );
+    }
+
+    #[test]
+    fn should_play_scripted_keys_per_frame_test() {
+        let mut input = ScriptedInput::new()
+            .press(0, Key::Char('p'))
+            .hold(1, 3, Key::Right);
+        assert_eq!(input.poll(), vec![Key::Char('p')]);
+        assert_eq!(input.poll(), vec![Key::Right]);
+        assert_eq!(input.poll(), vec![Key::Right]);
+        assert!(input.poll().is_empty());
+    }
+}
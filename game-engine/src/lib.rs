@@ -0,0 +1,12 @@
+pub mod ai;
+pub mod ecs;
+pub mod engine;
+pub mod framework;
+pub mod input;
+pub mod message;
+pub mod physics;
+pub mod random;
+pub mod render;
+pub mod replay;
+pub mod save;
+pub mod scene;
@@ -1,115 +1,535 @@
 use std::{
-    sync::mpsc::channel,
-    thread::{self, sleep},
-    time::Duration,
+    env,
+    error::Error,
+    io::{self, IsTerminal},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-use framework::*;
+use game_engine::ai::{self, BehaviourContext, BehaviourTree, Condition, Sequence, Status, Wait};
+use game_engine::ecs::*;
+use game_engine::engine::*;
+use game_engine::framework::*;
+use game_engine::input::*;
+use game_engine::message::*;
+use game_engine::physics::Transform;
+use game_engine::render::*;
+use game_engine::replay::*;
+use game_engine::save::*;
+use game_engine::scene::*;
 
-mod framework;
+const DEFAULT_SCENE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/scenes/level_1.json");
+const SAVE_FILE: &str = "savegame.json";
 
 fn main() {
-    let (transmitter, receiver) = channel::<String>();
+    // Kullanım:
+    //   game-engine [sahne dosyası]
+    //   game-engine --restore [kayıt dosyası]
+    //   game-engine --record <oturum dosyası> [sahne dosyası]
+    //   game-engine --replay <oturum dosyası> [sahne dosyası]
+    let args: Vec<String> = env::args().collect();
+    let registry = create_registry();
+    let mode = args.get(1).map(String::as_str);
+    let session_file = match mode {
+        Some("--record") | Some("--replay") => match args.get(2) {
+            Some(path) => Some(path.as_str()),
+            None => {
+                eprintln!("Error: session file is required");
+                return;
+            }
+        },
+        _ => None,
+    };
+    let loaded = match mode {
+        Some("--restore") => restore_game(
+            args.get(2).map(String::as_str).unwrap_or(SAVE_FILE),
+            &registry,
+        ),
+        Some("--record") | Some("--replay") => load_game(
+            args.get(3).map(String::as_str).unwrap_or(DEFAULT_SCENE),
+            &registry,
+        ),
+        scene_path => load_game(scene_path.unwrap_or(DEFAULT_SCENE), &registry),
+    };
+    let mut game = match loaded {
+        Ok(game) => game,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return;
+        }
+    };
+    spawn_goomba(&mut game);
 
-    let mut game = Game::new(transmitter.clone());
-    // let mut game = Game::default();
-    let super_mario = Player::new(1, "Super Mario".to_string());
-    let mushroom = Mushroom::new(2, 10);
-    game.add_actor(Box::new(super_mario));
-    game.add_actor(Box::new(mushroom));
+    let mut engine = GameEngine::new(EngineConfig::new(10, 5));
+    if io::stdout().is_terminal() {
+        engine.set_renderer(Box::new(TerminalRenderer::stdout(game.size())));
+    }
 
-    let mega_mind = MindController::default();
-    game.add_bot(Box::new(mega_mind));
-    game.add_bot(Box::new(Confuser::default()));
+    if let (Some("--replay"), Some(path)) = (mode, session_file) {
+        let result = Recording::read_from_file(path)
+            .and_then(|recording| replay(recording, &mut game, &mut engine, ReplayMode::Verify));
+        match result {
+            Ok(frames) => println!("{} frame kayıt ile aynı şekilde oynatıldı", frames),
+            Err(e) => eprintln!("Error: {}", e),
+        }
+        return;
+    }
 
-    // GameEngine::run(&game);
+    // Terminal kullanılamıyorsa (örneğin çıktı bir dosyaya yönlendirildiyse) oyun girdisiz çalışır
+    match TerminalInput::new() {
+        Ok(input) => game.set_input_source(Box::new(input)),
+        Err(e) => eprintln!("Keyboard input is not available. {}", e),
+    }
+    if session_file.is_some() {
+        game.start_recording(time_seed());
+    }
 
     game.apply();
-    game.draw();
-    game.update();
 
-    // drop(transmitter);
-    // Not: Sürekli dinlemede kalınacağı için loop döngüsü hiçbir zaman başlamaz.
-    for r in receiver {
-        println!("{}", r);
-    }
+    // Bot'lardan gelen mesajlar her frame'de oyun tarafından bloklanmadan okunur
+    engine.run(&mut game);
+    println!(
+        "{} frame, {} update işletildi. Ortalama FPS {:.2}",
+        engine.stats().frame_count(),
+        engine.stats().update_count(),
+        engine.stats().fps()
+    );
 
-    loop {
-        sleep(Duration::from_secs(2));
-        game.draw();
-        game.update();
-        // state check
+    if let (Some(path), Some(recording)) = (session_file, game.stop_recording()) {
+        match recording.write_to_file(path) {
+            Ok(_) => println!("Oturum {} dosyasına kaydedildi", path),
+            Err(e) => eprintln!("Error: {}", e),
+        }
+    }
+    match SaveGame::capture(&game, "Autosave").write_to_file(SAVE_FILE) {
+        Ok(_) => println!("Oyun {} dosyasına kaydedildi", SAVE_FILE),
+        Err(e) => eprintln!("Error: {}", e),
     }
 }
 
-#[derive(Default)]
-struct MindController {}
+// Davranışı aktör yerine bileşenlerle tanımlanan bir entity
+fn spawn_goomba(game: &mut Game) {
+    let world = game.world_mut();
+    let goomba = world.spawn();
+    world
+        .positions
+        .insert(goomba, Position { x: 100.0, y: 50.0 });
+    world
+        .velocities
+        .insert(goomba, Velocity { x: -20.0, y: 0.0 });
+    world.sprites.insert(goomba, Sprite { glyph: 'G' });
+    world.healths.insert(goomba, Health::new(3));
+}
+
+fn time_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+// Aktörler ve bot'lar sahne dosyasından yüklenir. Farklı bir seviye argüman olarak verilebilir.
+fn load_game(scene_path: &str, registry: &SceneRegistry) -> Result<Game, Box<dyn Error>> {
+    let mut game = Game::new();
+    let scene = SceneDefinition::from_file(scene_path)?;
+    registry.load(&scene, &mut game)?;
+    Ok(game)
+}
+
+fn restore_game(save_path: &str, registry: &SceneRegistry) -> Result<Game, Box<dyn Error>> {
+    let save = SaveGame::read_from_file(save_path, &Migrations::new())?;
+    Ok(save.restore(registry)?)
+}
+
+fn create_registry() -> SceneRegistry {
+    let mut registry = SceneRegistry::new();
+    registry.register_actor("player", |definition| {
+        let mut player = Player::new(
+            definition.id,
+            definition.properties.str("name")?.to_string(),
+            definition.properties.transform()?,
+        );
+        player.strength = definition.properties.or("strength", 1, Properties::u8)?;
+        Ok(Box::new(player))
+    });
+    registry.register_actor("mushroom", |definition| {
+        let mut mushroom = Mushroom::new(
+            definition.id,
+            definition.properties.u8("strength")?,
+            definition.properties.transform()?,
+        );
+        mushroom.is_eaten = definition.properties.or("eaten", false, Properties::bool)?;
+        Ok(Box::new(mushroom))
+    });
+    registry.register_bot("mind_controller", |definition| {
+        Ok(Box::new(MindController::new(
+            definition.properties.u32("target")?,
+        )))
+    });
+    registry.register_bot("confuser", |definition| {
+        Ok(Box::new(Confuser::new(
+            definition.properties.u32("target")?,
+        )))
+    });
+    registry
+}
+
+struct MindController {
+    target: u32,
+}
+
+impl MindController {
+    pub fn new(target: u32) -> Self {
+        MindController { target }
+    }
+}
 
+// Hedef mantar yenmediği sürece her 5 saniyede bir gücünü artırır
 impl Bot for MindController {
-    fn apply(&self) {
-        thread::spawn(move || {
-            loop {
-                println!("\tApplying simulation...{:?}", thread::current().id());
-                sleep(Duration::from_secs(5));
-            }
-        });
+    fn behaviour(&self) -> Option<BehaviourTree> {
+        let target = self.target;
+        // Mantar bekleme sırasında yenmiş olabileceği için koşul beklemeden sonra kontrol edilir
+        Some(BehaviourTree::new(Box::new(Sequence::new(vec![
+            Box::new(Wait::new(5.0)),
+            Box::new(Condition::new(move |context: &BehaviourContext| {
+                context
+                    .find_actor(target)
+                    .is_some_and(|actor| actor.transform().is_some())
+            })),
+            Box::new(ai::Action::new(move |context: &mut BehaviourContext| {
+                context.send(BotCommand::ChangeStrength {
+                    actor_id: target,
+                    amount: 1,
+                });
+                Status::Success
+            })),
+        ]))))
+    }
+    fn kind(&self) -> &'static str {
+        "mind_controller"
     }
+    fn save(&self, properties: &mut Properties) {
+        properties.set("target", self.target);
+    }
+}
+
+struct Confuser {
+    target: u32,
 }
 
-#[derive(Default)]
-struct Confuser {}
+impl Confuser {
+    pub fn new(target: u32) -> Self {
+        Confuser { target }
+    }
+}
 
+// Hedef oyuncu bir mantara yaklaştığında saniyede bir kafasını karıştırır
 impl Bot for Confuser {
-    fn apply(&self) {
-        thread::spawn(|| {
-            loop {
-                println!(
-                    "\t\tBrain confusing in progress...{:?}",
-                    thread::current().id()
-                );
-                sleep(Duration::from_secs(1));
-            }
-        });
+    fn behaviour(&self) -> Option<BehaviourTree> {
+        let target = self.target;
+        Some(BehaviourTree::new(Box::new(Sequence::new(vec![
+            Box::new(Wait::new(1.0)),
+            Box::new(Condition::new(move |context: &BehaviourContext| {
+                is_near_mushroom(context, target)
+            })),
+            Box::new(ai::Action::new(move |context: &mut BehaviourContext| {
+                context.send(BotCommand::Confuse {
+                    actor_id: target,
+                    seconds: 0.5,
+                });
+                Status::Success
+            })),
+        ]))))
+    }
+    fn kind(&self) -> &'static str {
+        "confuser"
+    }
+    fn save(&self, properties: &mut Properties) {
+        properties.set("target", self.target);
     }
 }
 
-#[allow(dead_code)]
+// Bu mesafeden yakındaki mantarlar oyuncunun hedefi kabul edilir
+const CONFUSE_DISTANCE: f32 = 200.0;
+
+fn is_near_mushroom(context: &BehaviourContext, actor_id: u32) -> bool {
+    let Some(position) = context
+        .find_actor(actor_id)
+        .and_then(|actor| actor.transform())
+        .map(|transform| transform.position)
+    else {
+        return false;
+    };
+    context
+        .actors_of_kind("mushroom")
+        .filter_map(|mushroom| mushroom.transform())
+        .any(|transform| {
+            let dx = transform.position.x - position.x;
+            let dy = transform.position.y - position.y;
+            (dx * dx + dy * dy).sqrt() <= CONFUSE_DISTANCE
+        })
+}
+
+// Oyuncunun oyunda kalabileceği süre (saniye). Süre dolunca oyunun kapatılması talep edilir.
+const PLAY_TIME_LIMIT: f32 = 30.0;
+// Oyuncunun saniyede kat ettiği mesafe
+const PLAYER_SPEED: f32 = 160.0;
+
 struct Player {
     id: u32,
     name: String,
+    strength: u8,
+    transform: Transform,
+    play_time: f32,
+    confused_for: f32,
 }
 impl Player {
-    pub fn new(id: u32, name: String) -> Self {
-        Player { id, name }
+    pub fn new(id: u32, name: String, transform: Transform) -> Self {
+        Player {
+            id,
+            name,
+            strength: 1,
+            transform,
+            play_time: 0.0,
+            confused_for: 0.0,
+        }
     }
 }
 
 impl Actor for Player {
-    fn draw(&self) {
-        println!("Player draw");
+    fn id(&self) -> u32 {
+        self.id
     }
-    fn update(&mut self) {
-        println!("Update location of player");
+    fn kind(&self) -> &'static str {
+        "player"
+    }
+    fn strength(&self) -> u8 {
+        self.strength
+    }
+    fn transform(&self) -> Option<&Transform> {
+        Some(&self.transform)
+    }
+    fn transform_mut(&mut self) -> Option<&mut Transform> {
+        Some(&mut self.transform)
+    }
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        renderer.draw_glyph(self.transform.position, 'M');
+        let label = Position {
+            x: self.transform.position.x,
+            y: self.transform.position.y - CELL_HEIGHT,
+        };
+        renderer.draw_text(label, &format!("{} ({})", self.name, self.strength));
+    }
+    fn update(&mut self, context: &mut UpdateContext) {
+        let input = context.input();
+        let mut direction = (0.0, 0.0);
+        if input.is_pressed(Action::MoveLeft) {
+            direction.0 -= 1.0;
+        }
+        if input.is_pressed(Action::MoveRight) {
+            direction.0 += 1.0;
+        }
+        if input.is_pressed(Action::MoveUp) {
+            direction.1 -= 1.0;
+        }
+        if input.is_pressed(Action::MoveDown) {
+            direction.1 += 1.0;
+        }
+        // Kafası karışan oyuncu ters yöne hareket eder
+        if self.confused_for > 0.0 {
+            self.confused_for -= context.delta_time();
+            direction = (-direction.0, -direction.1);
+        }
+        self.transform.position.x += direction.0 * PLAYER_SPEED * context.delta_time();
+        self.transform.position.y += direction.1 * PLAYER_SPEED * context.delta_time();
+
+        self.play_time += context.delta_time();
+        if self.play_time >= PLAY_TIME_LIMIT {
+            context.request(EngineRequest::Shutdown);
+        }
+    }
+    fn on_collision(&mut self, other: &dyn Actor) {
+        if other.kind() == "mushroom" {
+            self.strength = self.strength.saturating_add(other.strength());
+        }
+    }
+    fn on_command(&mut self, command: &BotCommand) {
+        if let BotCommand::Confuse { seconds, .. } = command {
+            self.confused_for = self.confused_for.max(*seconds);
+        }
+    }
+    fn save(&self, properties: &mut Properties) {
+        properties.set("name", self.name.as_str());
+        properties.set("strength", self.strength);
+        properties.set_transform(&self.transform);
     }
 }
 
-#[allow(dead_code)]
 struct Mushroom {
     id: u32,
     strength: u8,
+    transform: Transform,
+    is_eaten: bool,
 }
 
 impl Mushroom {
-    pub fn new(id: u32, strength: u8) -> Self {
-        Mushroom { id, strength }
+    pub fn new(id: u32, strength: u8, transform: Transform) -> Self {
+        Mushroom {
+            id,
+            strength,
+            transform,
+            is_eaten: false,
+        }
     }
 }
 
 impl Actor for Mushroom {
-    fn draw(&self) {
-        println!("Mushroom drawing");
+    fn id(&self) -> u32 {
+        self.id
+    }
+    fn kind(&self) -> &'static str {
+        "mushroom"
+    }
+    fn strength(&self) -> u8 {
+        self.strength
+    }
+    // Yenen mantar artık dünyada yer kaplamaz
+    fn transform(&self) -> Option<&Transform> {
+        (!self.is_eaten).then_some(&self.transform)
+    }
+    fn transform_mut(&mut self) -> Option<&mut Transform> {
+        (!self.is_eaten).then_some(&mut self.transform)
+    }
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        if !self.is_eaten {
+            renderer.draw_glyph(self.transform.position, 'm');
+        }
+    }
+    fn on_collision(&mut self, other: &dyn Actor) {
+        if other.kind() == "player" {
+            self.is_eaten = true;
+        }
+    }
+    // Yenen mantar frame sonunda dünyadan kaldırılır
+    fn update(&mut self, context: &mut UpdateContext) {
+        if self.is_eaten {
+            context.destroy(self.id);
+        }
+    }
+    fn on_command(&mut self, command: &BotCommand) {
+        if let BotCommand::ChangeStrength { amount, .. } = command {
+            self.strength = (self.strength as i16)
+                .saturating_add(*amount)
+                .clamp(0, u8::MAX as i16) as u8;
+        }
+    }
+    fn save(&self, properties: &mut Properties) {
+        properties.set("strength", self.strength);
+        properties.set("eaten", self.is_eaten);
+        properties.set_transform(&self.transform);
     }
-    fn update(&mut self) {
-        println!("Calculation strength");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn create_game(input: ScriptedInput) -> Game {
+        let mut game = Game::new();
+        game.add_actor(Box::new(Player::new(
+            1,
+            "Super Mario".to_string(),
+            Transform::new(100.0, 100.0, 32.0, 32.0),
+        )));
+        game.set_input_source(Box::new(input));
+        game
+    }
+
+    fn player_position(game: &mut Game) -> (f32, f32) {
+        let position = game
+            .world_mut()
+            .find_actor_mut(1)
+            .and_then(|player| player.transform().copied())
+            .unwrap()
+            .position;
+        (position.x, position.y)
+    }
+
+    #[test]
+    fn should_move_player_with_scripted_input_test() {
+        let input = ScriptedInput::new()
+            .hold(0, 3, Key::Right)
+            .press(3, Key::Char('w'))
+            .press(4, Key::Char('x'));
+        let mut game = create_game(input);
+        let mut engine = GameEngine::new(EngineConfig::new(10, 60));
+
+        for _ in 0..5 {
+            engine.frame(&mut game, Duration::from_millis(100));
+        }
+
+        let (x, y) = player_position(&mut game);
+        assert!((x - 148.0).abs() < 0.01);
+        assert!((y - 84.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn should_invert_movement_of_confused_player_test() {
+        let mut game = create_game(ScriptedInput::new().hold(0, 2, Key::Left));
+        game.sender()
+            .send(GameMessage::BotCommand {
+                bot: 0,
+                command: BotCommand::Confuse {
+                    actor_id: 1,
+                    seconds: 0.1,
+                },
+            })
+            .unwrap();
+        let mut engine = GameEngine::new(EngineConfig::new(10, 60));
+
+        engine.frame(&mut game, Duration::from_millis(100));
+        assert!((player_position(&mut game).0 - 116.0).abs() < 0.01);
+        engine.frame(&mut game, Duration::from_millis(100));
+        assert!((player_position(&mut game).0 - 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn should_draw_actors_and_score_snapshot_test() {
+        let mut game = Game::new();
+        game.set_size(Size::new(192.0, 128.0));
+        game.add_actor(Box::new(Player::new(
+            1,
+            "Mario".to_string(),
+            Transform::new(32.0, 64.0, 32.0, 32.0),
+        )));
+        game.add_actor(Box::new(Mushroom::new(
+            2,
+            10,
+            Transform::new(112.0, 64.0, 16.0, 16.0),
+        )));
+        game.set_score(7);
+
+        let mut renderer = HeadlessRenderer::new(game.size());
+        renderer.begin_frame();
+        game.draw(&mut renderer);
+        renderer.present();
+
+        assert_eq!(
+            renderer.last_frame().unwrap(),
+            "Score 7\n  Mario (1)\n  M    m\n"
+        );
+    }
+
+    #[test]
+    fn should_clamp_mushroom_strength_test() {
+        let mut mushroom = Mushroom::new(2, 250, Transform::new(0.0, 0.0, 16.0, 16.0));
+        let change = |amount| BotCommand::ChangeStrength {
+            actor_id: 2,
+            amount,
+        };
+        mushroom.on_command(&change(i16::MAX));
+        assert_eq!(mushroom.strength(), u8::MAX);
+        mushroom.on_command(&change(i16::MIN));
+        assert_eq!(mushroom.strength(), 0);
     }
 }
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Oyun ile bot'lar arasında kanal üzerinden taşınan mesajlar.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GameMessage {
+    ActorSpawned { id: u32 },
+    ActorDestroyed { id: u32 },
+    BotCommand { bot: usize, command: BotCommand },
+    ScoreChanged { delta: i32 },
+    Shutdown,
+}
+
+/// Bot'ların aktörler üzerinde uygulamak istediği komutlar.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BotCommand {
+    ChangeStrength { actor_id: u32, amount: i16 },
+    Confuse { actor_id: u32, seconds: f32 },
+}
+
+impl BotCommand {
+    /// Komutun uygulanacağı aktörün id değeri
+    pub fn target(&self) -> u32 {
+        match self {
+            BotCommand::ChangeStrength { actor_id, .. } | BotCommand::Confuse { actor_id, .. } => {
+                *actor_id
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use crate::ecs::{Entity, Position, World};
+use crate::framework::Size;
+
+/// Aktörün dünyadaki konumu ve kapladığı alan.
+///
+/// `position` nesnenin sol üst köşesini ifade eder.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Transform {
+    pub position: Position,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Transform {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Transform {
+            position: Position { x, y },
+            width,
+            height,
+        }
+    }
+    pub fn bounds(&self) -> Aabb {
+        Aabb {
+            min: self.position,
+            max: Position {
+                x: self.position.x + self.width,
+                y: self.position.y + self.height,
+            },
+        }
+    }
+}
+
+/// Eksenlere hizalı sınır kutusu (Axis-Aligned Bounding Box)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: Position,
+    pub max: Position,
+}
+
+impl Aabb {
+    /// Kenarların sadece birbirine değmesi çakışma olarak kabul edilmez.
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min.x < other.max.x
+            && self.max.x > other.min.x
+            && self.min.y < other.max.y
+            && self.max.y > other.min.y
+    }
+}
+
+/// Dünya sınırlarının dışına çıkan nesnelere ne yapılacağını belirler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoundsMode {
+    /// Nesne sınırda durdurulur
+    #[default]
+    Clamp,
+    /// Nesne dünyanın diğer tarafından tekrar girer
+    Wrap,
+}
+
+impl BoundsMode {
+    pub fn apply(&self, position: &mut Position, width: f32, height: f32, size: &Size) {
+        let max_x = (size.width() - width).max(0.0);
+        let max_y = (size.height() - height).max(0.0);
+        match self {
+            BoundsMode::Clamp => {
+                position.x = position.x.clamp(0.0, max_x);
+                position.y = position.y.clamp(0.0, max_y);
+            }
+            BoundsMode::Wrap => {
+                position.x = wrap(position.x, size.width());
+                position.y = wrap(position.y, size.height());
+            }
+        }
+    }
+}
+
+// Boyutu sıfır olan dünyada kalan tek konum 0'dır, `rem_euclid` burada NaN üretir
+fn wrap(value: f32, size: f32) -> f32 {
+    if size > 0.0 {
+        value.rem_euclid(size)
+    } else {
+        0.0
+    }
+}
+
+/// Aktörleri ve konum bileşeni olan entity'leri dünya sınırları içerisinde tutar.
+pub fn keep_in_bounds(world: &mut World, size: &Size, mode: BoundsMode) {
+    for (_, actor) in world.actors.iter_mut() {
+        if let Some(transform) = actor.transform_mut() {
+            mode.apply(
+                &mut transform.position,
+                transform.width,
+                transform.height,
+                size,
+            );
+        }
+    }
+    for (_, position) in world.positions.iter_mut() {
+        mode.apply(position, 0.0, 0.0, size);
+    }
+}
+
+/// Sınır kutuları çakışan aktör çiftlerini bulur.
+pub fn find_collisions(world: &World) -> Vec<(Entity, Entity)> {
+    let bounds: Vec<(Entity, Aabb)> = world
+        .actors
+        .iter()
+        .filter_map(|(entity, actor)| actor.transform().map(|t| (entity, t.bounds())))
+        .collect();
+
+    let mut pairs = Vec::new();
+    for (i, (first, first_bounds)) in bounds.iter().enumerate() {
+        for (second, second_bounds) in &bounds[i + 1..] {
+            if first_bounds.intersects(second_bounds) {
+                pairs.push((*first, *second));
+            }
+        }
+    }
+    pairs
+}
+
+/// Çakışan her iki aktöre de diğerini bildirir.
+pub fn notify_collisions(world: &mut World, pairs: &[(Entity, Entity)]) {
+    for (first, second) in pairs {
+        notify(world, *first, *second);
+        notify(world, *second, *first);
+    }
+}
+
+fn notify(world: &mut World, target: Entity, other: Entity) {
+    // Aynı koleksiyondan iki nesneye aynı anda erişebilmek için hedef geçici olarak çıkartılır
+    let Some(mut actor) = world.actors.remove(target) else {
+        return;
+    };
+    if let Some(other_actor) = world.actors.get(other) {
+        actor.on_collision(other_actor.as_ref());
+    }
+    world.actors.insert(target, actor);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::{Actor, UpdateContext};
+    use crate::render::Renderer;
+
+    struct Block {
+        id: u32,
+        transform: Transform,
+        hits: Vec<u32>,
+    }
+
+    impl Actor for Block {
+        fn id(&self) -> u32 {
+            self.id
+        }
+        fn draw(&self, _renderer: &mut dyn Renderer) {}
+        fn update(&mut self, _context: &mut UpdateContext) {}
+        fn transform(&self) -> Option<&Transform> {
+            Some(&self.transform)
+        }
+        fn transform_mut(&mut self) -> Option<&mut Transform> {
+            Some(&mut self.transform)
+        }
+        fn on_collision(&mut self, other: &dyn Actor) {
+            self.hits.push(other.id());
+        }
+        fn strength(&self) -> u8 {
+            self.hits.len() as u8
+        }
+    }
+
+    fn block(id: u32, x: f32, y: f32) -> Box<Block> {
+        Box::new(Block {
+            id,
+            transform: Transform::new(x, y, 10.0, 10.0),
+            hits: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn should_clamp_and_wrap_positions_test() {
+        let size = Size::new(100.0, 50.0);
+        let mut position = Position { x: 120.0, y: -5.0 };
+        BoundsMode::Clamp.apply(&mut position, 10.0, 10.0, &size);
+        assert_eq!(position, Position { x: 90.0, y: 0.0 });
+
+        let mut position = Position { x: 120.0, y: -5.0 };
+        BoundsMode::Wrap.apply(&mut position, 10.0, 10.0, &size);
+        assert_eq!(position, Position { x: 20.0, y: 45.0 });
+
+        let mut position = Position { x: 120.0, y: -5.0 };
+        BoundsMode::Wrap.apply(&mut position, 10.0, 10.0, &Size::new(0.0, 0.0));
+        assert_eq!(position, Position { x: 0.0, y: 0.0 });
+    }
+
+    #[test]
+    fn should_report_overlapping_pairs_to_both_actors_test() {
+        let mut world = World::new();
+        let first = world.spawn_actor(block(1, 0.0, 0.0));
+        let second = world.spawn_actor(block(2, 5.0, 5.0));
+        world.spawn_actor(block(3, 30.0, 0.0));
+
+        let pairs = find_collisions(&world);
+        assert_eq!(pairs, vec![(first, second)]);
+
+        notify_collisions(&mut world, &pairs);
+        assert_eq!(world.actors.get(first).unwrap().strength(), 1);
+        assert_eq!(world.actors.get(second).unwrap().strength(), 1);
+        assert_eq!(world.find_actor_mut(3).unwrap().strength(), 0);
+    }
+}
@@ -0,0 +1,67 @@
+/// Oyun içerisinde kullanılan tohum (seed) değerli sözde rastgele sayı üreteci.
+///
+/// Aynı tohumla oluşturulan üreteçler aynı sayı dizisini üretir. Kayıtların tekrar
+/// oynatılabilmesi için oyundaki rastgelelik sadece bu üreteç üzerinden sağlanmalıdır.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Random {
+    seed: u64,
+    state: u64,
+}
+
+impl Default for Random {
+    fn default() -> Self {
+        Self::new(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Random {
+            seed,
+            // xorshift sıfır durumundan çıkamaz
+            state: if seed == 0 {
+                0x9e37_79b9_7f4a_7c15
+            } else {
+                seed
+            },
+        }
+    }
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+    /// xorshift64* algoritması ile bir sonraki sayıyı üretir
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+    /// [0, 1) aralığında bir sayı üretir
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+    /// [min, max) aralığında bir sayı üretir
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_repeat_sequence_for_same_seed_test() {
+        let mut first = Random::new(42);
+        let mut second = Random::new(42);
+        let numbers: Vec<u64> = (0..5).map(|_| first.next_u64()).collect();
+        assert_eq!(
+            numbers,
+            (0..5).map(|_| second.next_u64()).collect::<Vec<_>>()
+        );
+        assert_ne!(Random::new(43).next_u64(), numbers[0]);
+
+        let value = Random::new(0).range(2.0, 4.0);
+        assert!((2.0..4.0).contains(&value));
+    }
+}
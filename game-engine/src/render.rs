@@ -0,0 +1,278 @@
+use crate::ecs::Position;
+use crate::framework::Size;
+use std::fmt::{Display, Formatter};
+use std::io::{self, Write};
+
+/// Terminaldeki bir karakterin dünyada kapladığı alan. Terminal karakterleri yaklaşık
+/// olarak eninin iki katı yüksekliğe sahip olduğu için hücreler de bu oranda seçilmiştir.
+pub const CELL_WIDTH: f32 = 16.0;
+pub const CELL_HEIGHT: f32 = 32.0;
+
+/// Dünyanın karakterlerden oluşan ızgara karşılığı.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Canvas {
+    columns: usize,
+    rows: usize,
+    cells: Vec<char>,
+}
+
+impl Canvas {
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Canvas {
+            columns,
+            rows,
+            cells: vec![' '; columns * rows],
+        }
+    }
+    pub fn from_size(size: &Size) -> Self {
+        let columns = (size.width() / CELL_WIDTH).ceil().max(1.0) as usize;
+        let rows = (size.height() / CELL_HEIGHT).ceil().max(1.0) as usize;
+        Self::new(columns, rows)
+    }
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+    pub fn clear(&mut self) {
+        self.cells.fill(' ');
+    }
+    pub fn get(&self, column: usize, row: usize) -> Option<char> {
+        (column < self.columns && row < self.rows).then(|| self.cells[row * self.columns + column])
+    }
+    /// Izgara dışında kalan hücreler sessizce göz ardı edilir.
+    pub fn set(&mut self, column: usize, row: usize, glyph: char) {
+        if column < self.columns && row < self.rows {
+            self.cells[row * self.columns + column] = glyph;
+        }
+    }
+    /// Dünya koordinatının düştüğü hücreyi bulur
+    pub fn cell_of(&self, position: Position) -> Option<(usize, usize)> {
+        if position.x < 0.0 || position.y < 0.0 {
+            return None;
+        }
+        let column = (position.x / CELL_WIDTH) as usize;
+        let row = (position.y / CELL_HEIGHT) as usize;
+        (column < self.columns && row < self.rows).then_some((column, row))
+    }
+    pub fn put_glyph(&mut self, position: Position, glyph: char) {
+        if let Some((column, row)) = self.cell_of(position) {
+            self.set(column, row, glyph);
+        }
+    }
+    /// Metni konumdan başlayarak sağa doğru yazar, satıra sığmayan kısım kesilir.
+    pub fn put_text(&mut self, position: Position, text: &str) {
+        if let Some((column, row)) = self.cell_of(position) {
+            for (offset, glyph) in text.chars().enumerate() {
+                self.set(column + offset, row, glyph);
+            }
+        }
+    }
+    pub fn row(&self, row: usize) -> String {
+        self.cells[row * self.columns..(row + 1) * self.columns]
+            .iter()
+            .collect()
+    }
+}
+
+impl Display for Canvas {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.rows {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", self.row(row))?;
+        }
+        Ok(())
+    }
+}
+
+/// Aktörlerin ve oyunun çizim yaptığı hedef.
+///
+/// Çizimler önce bir arka tampona (canvas) yapılır, `present` çağrıldığında ekrana yansıtılır.
+pub trait Renderer {
+    fn canvas(&mut self) -> &mut Canvas;
+    fn present(&mut self);
+
+    fn begin_frame(&mut self) {
+        self.canvas().clear();
+    }
+    fn draw_glyph(&mut self, position: Position, glyph: char) {
+        self.canvas().put_glyph(position, glyph);
+    }
+    fn draw_text(&mut self, position: Position, text: &str) {
+        self.canvas().put_text(position, text);
+    }
+}
+
+/// ANSI kaçış kodları ile terminale çizim yapan renderer.
+///
+/// Ekrandaki görüntü ön tamponda saklanır ve her frame'de sadece değişen hücreler yazılır.
+pub struct TerminalRenderer<W: Write> {
+    front: Canvas,
+    back: Canvas,
+    output: W,
+    is_initialized: bool,
+}
+
+impl TerminalRenderer<io::Stdout> {
+    pub fn stdout(size: &Size) -> Self {
+        Self::new(size, io::stdout())
+    }
+}
+
+impl<W: Write> TerminalRenderer<W> {
+    pub fn new(size: &Size, output: W) -> Self {
+        let back = Canvas::from_size(size);
+        TerminalRenderer {
+            front: back.clone(),
+            back,
+            output,
+            is_initialized: false,
+        }
+    }
+
+    fn write_changes(&mut self) -> io::Result<()> {
+        if !self.is_initialized {
+            // Ekranı temizle ve imleci gizle
+            write!(self.output, "\x1b[2J\x1b[?25l")?;
+            self.front.cells.fill('\0');
+            self.is_initialized = true;
+        }
+        for row in 0..self.back.rows {
+            let mut cursor_column = None;
+            for column in 0..self.back.columns {
+                let glyph = self.back.cells[row * self.back.columns + column];
+                if self.front.cells[row * self.front.columns + column] == glyph {
+                    continue;
+                }
+                // Ardışık değişikliklerde imleç zaten doğru yerde olduğu için tekrar taşınmaz
+                if cursor_column != Some(column) {
+                    write!(self.output, "\x1b[{};{}H", row + 1, column + 1)?;
+                }
+                write!(self.output, "{}", glyph)?;
+                cursor_column = Some(column + 1);
+            }
+        }
+        self.output.flush()?;
+        std::mem::swap(&mut self.front, &mut self.back);
+        self.back.cells.copy_from_slice(&self.front.cells);
+        Ok(())
+    }
+
+    pub fn output(&self) -> &W {
+        &self.output
+    }
+}
+
+impl<W: Write> Renderer for TerminalRenderer<W> {
+    fn canvas(&mut self) -> &mut Canvas {
+        &mut self.back
+    }
+    fn present(&mut self) {
+        if let Err(e) = self.write_changes() {
+            eprintln!("Frame could not be written to terminal. {}", e);
+        }
+    }
+}
+
+impl<W: Write> Drop for TerminalRenderer<W> {
+    fn drop(&mut self) {
+        if self.is_initialized {
+            // İmleci geri getir ve çizim alanının altına taşı
+            let _ = write!(self.output, "\x1b[?25h\x1b[{};1H", self.front.rows + 1);
+            let _ = self.output.flush();
+        }
+    }
+}
+
+/// Ekrana hiçbir şey yazmadan frame'leri biriktiren renderer. Testlerde kullanılır.
+pub struct HeadlessRenderer {
+    canvas: Canvas,
+    frames: Vec<String>,
+}
+
+impl HeadlessRenderer {
+    pub fn new(size: &Size) -> Self {
+        HeadlessRenderer {
+            canvas: Canvas::from_size(size),
+            frames: Vec::new(),
+        }
+    }
+    /// Frame'ler satır sonundaki boşluklar atılmış metinler olarak saklanır.
+    pub fn frames(&self) -> &[String] {
+        &self.frames
+    }
+    pub fn last_frame(&self) -> Option<&str> {
+        self.frames.last().map(String::as_str)
+    }
+}
+
+impl Renderer for HeadlessRenderer {
+    fn canvas(&mut self) -> &mut Canvas {
+        &mut self.canvas
+    }
+    fn present(&mut self) {
+        let frame = (0..self.canvas.rows)
+            .map(|row| self.canvas.row(row).trim_end().to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.frames.push(frame);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(x: f32, y: f32) -> Position {
+        Position { x, y }
+    }
+
+    #[test]
+    fn should_map_world_positions_to_cells_test() {
+        let mut canvas = Canvas::from_size(&Size::new(160.0, 96.0));
+        assert_eq!((canvas.columns(), canvas.rows()), (10, 3));
+
+        canvas.put_glyph(position(20.0, 40.0), 'M');
+        canvas.put_glyph(position(-1.0, 0.0), 'x');
+        canvas.put_glyph(position(500.0, 0.0), 'x');
+        canvas.put_text(position(112.0, 64.0), "Score");
+
+        assert_eq!(canvas.get(1, 1), Some('M'));
+        assert_eq!(canvas.to_string(), "          \n M        \n       Sco");
+    }
+
+    #[test]
+    fn should_capture_frames_in_headless_renderer_test() {
+        let mut renderer = HeadlessRenderer::new(&Size::new(160.0, 64.0));
+        renderer.begin_frame();
+        renderer.draw_glyph(position(0.0, 32.0), '@');
+        renderer.present();
+        renderer.begin_frame();
+        renderer.draw_text(position(32.0, 0.0), "Hi");
+        renderer.present();
+
+        assert_eq!(
+            renderer.frames(),
+            &["\n@".to_string(), "  Hi\n".to_string()]
+        );
+    }
+
+    #[test]
+    fn should_write_only_changed_cells_to_terminal_test() {
+        let mut renderer = TerminalRenderer::new(&Size::new(64.0, 32.0), Vec::new());
+        renderer.begin_frame();
+        renderer.draw_text(position(0.0, 0.0), "ab");
+        renderer.present();
+        let first = String::from_utf8(renderer.output().clone()).unwrap();
+        assert_eq!(first, "\x1b[2J\x1b[?25l\x1b[1;1Hab  ");
+
+        renderer.begin_frame();
+        renderer.draw_text(position(0.0, 0.0), "ac");
+        renderer.present();
+        let second = String::from_utf8(renderer.output().clone()).unwrap();
+        assert_eq!(&second[first.len()..], "\x1b[1;2Hc");
+    }
+}
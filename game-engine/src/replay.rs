@@ -0,0 +1,380 @@
+use crate::engine::GameEngine;
+use crate::framework::Game;
+use crate::input::Key;
+use crate::message::GameMessage;
+use crate::save::SaveGame;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::time::Duration;
+
+/// Bir oyun oturumunun frame frame kaydı.
+///
+/// Oyunun dışarıdan etkilendiği her şey kaydedilir: rastgele sayı üretecinin tohumu, frame
+/// süreleri, basılan tuşlar, kanaldan gelen mesajlar ve bot'ların verdiği komutlar.
+/// Kayıt, alındığı sahne ve motor ayarları ile oynatıldığında aynı sonucu üretir.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recording {
+    pub seed: u64,
+    pub frames: Vec<FrameRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameRecord {
+    pub elapsed: Duration,
+    pub keys: Vec<Key>,
+    /// Frame içerisindeki her update'te işlenen mesajlar
+    pub updates: Vec<Vec<GameMessage>>,
+    /// Frame sonundaki oyun durumunun özeti. Bkz. `state_hash`
+    pub state: u64,
+}
+
+impl Recording {
+    pub fn new(seed: u64) -> Self {
+        Recording {
+            seed,
+            frames: Vec::new(),
+        }
+    }
+    pub fn to_json(&self) -> Result<String, ReplayError> {
+        serde_json::to_string(self).map_err(ReplayError::Parse)
+    }
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ReplayError> {
+        std::fs::write(path, self.to_json()?).map_err(ReplayError::Io)
+    }
+    pub fn parse(content: &str) -> Result<Self, ReplayError> {
+        serde_json::from_str(content).map_err(ReplayError::Parse)
+    }
+    pub fn read_from_file<P: AsRef<Path>>(path: P) -> Result<Self, ReplayError> {
+        let content = std::fs::read_to_string(path).map_err(ReplayError::Io)?;
+        Self::parse(&content)
+    }
+}
+
+/// Oyunun o anki durumunu kaydetme veya oynatma durumuna göre yönetir.
+pub(crate) enum Session {
+    Live,
+    Recording(Recording),
+    Replaying {
+        recording: Recording,
+        frame: usize,
+        update: usize,
+    },
+}
+
+impl Session {
+    pub(crate) fn is_recording(&self) -> bool {
+        matches!(self, Session::Recording(_))
+    }
+    pub(crate) fn is_replaying(&self) -> bool {
+        matches!(self, Session::Replaying { .. })
+    }
+    pub(crate) fn begin_frame(&mut self, elapsed: Duration, keys: &[Key]) {
+        if let Session::Recording(recording) = self {
+            recording.frames.push(FrameRecord {
+                elapsed,
+                keys: keys.to_vec(),
+                updates: Vec::new(),
+                state: 0,
+            });
+        }
+    }
+    /// Oynatılan frame'de basılan tuşlar. Kayıt bittiyse boş döner.
+    pub(crate) fn replayed_keys(&self) -> Vec<Key> {
+        match self {
+            Session::Replaying {
+                recording, frame, ..
+            } => recording
+                .frames
+                .get(*frame)
+                .map(|record| record.keys.clone())
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+    pub(crate) fn begin_update(&mut self) {
+        if let Session::Recording(recording) = self
+            && let Some(record) = recording.frames.last_mut()
+        {
+            record.updates.push(Vec::new());
+        }
+    }
+    pub(crate) fn record(&mut self, message: &GameMessage) {
+        if let Session::Recording(recording) = self
+            && let Some(messages) = recording
+                .frames
+                .last_mut()
+                .and_then(|record| record.updates.last_mut())
+        {
+            messages.push(message.clone());
+        }
+    }
+    /// Oynatılan update'te işlenmesi gereken mesajları döner ve bir sonraki update'e geçer.
+    pub(crate) fn next_replayed_update(&mut self) -> Vec<GameMessage> {
+        match self {
+            Session::Replaying {
+                recording,
+                frame,
+                update,
+            } => {
+                let messages = recording
+                    .frames
+                    .get(*frame)
+                    .and_then(|record| record.updates.get(*update))
+                    .cloned()
+                    .unwrap_or_default();
+                *update += 1;
+                messages
+            }
+            _ => Vec::new(),
+        }
+    }
+    /// Kayıt yapılıyorsa frame sonundaki durum özeti de verilmelidir.
+    pub(crate) fn end_frame(&mut self, state: Option<u64>) {
+        match self {
+            Session::Recording(recording) => {
+                if let Some(record) = recording.frames.last_mut() {
+                    record.state = state.unwrap_or_default();
+                }
+            }
+            Session::Replaying { frame, update, .. } => {
+                *frame += 1;
+                *update = 0;
+            }
+            Session::Live => {}
+        }
+    }
+}
+
+/// Oyundaki aktörlerin, bileşenlerin ve skorun özetini hesaplar.
+///
+/// Aktörlerin durumu kayıt dosyasına yazdıkları özellikler üzerinden karşılaştırılır. Özet
+/// kayıt dosyasında saklandığından Rust sürümüne bağlı olmayan FNV-1a kullanılır.
+pub fn state_hash(game: &Game) -> u64 {
+    let save = SaveGame::capture(game, "");
+    let mut state = serde_json::to_string(&save.scene.actors).unwrap_or_default();
+    state.push_str(&format!("|{}", save.score));
+    let world = game.world();
+    for (entity, position) in world.positions.iter() {
+        state.push_str(&format!("|{entity:?}{position:?}"));
+    }
+    for (entity, health) in world.healths.iter() {
+        state.push_str(&format!("|{entity:?}{health:?}"));
+    }
+    fnv1a(state.as_bytes())
+}
+
+/// 64 bit FNV-1a
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayMode {
+    /// Kayıt sadece oynatılır
+    Play,
+    /// Her frame sonunda oyunun durumu kayıttaki ile karşılaştırılır
+    Verify,
+}
+
+/// Kaydı verilen oyun ve motor üzerinde frame frame oynatır ve oynatılan frame sayısını döner.
+///
+/// Oyun kaydın alındığı sahne ile oluşturulmuş olmalı ve `Game::apply` çağrılmamış olmalıdır.
+/// Oynatma sırasında bot'lar çalıştırılmaz, verdikleri komutlar kayıttan okunur.
+pub fn replay(
+    recording: Recording,
+    game: &mut Game,
+    engine: &mut GameEngine,
+    mode: ReplayMode,
+) -> Result<usize, ReplayError> {
+    let frames: Vec<(Duration, u64)> = recording
+        .frames
+        .iter()
+        .map(|record| (record.elapsed, record.state))
+        .collect();
+    game.start_replay(recording);
+    let mut played = 0;
+    for (frame, (elapsed, expected)) in frames.into_iter().enumerate() {
+        engine.frame(game, elapsed);
+        played += 1;
+        if mode == ReplayMode::Verify {
+            let actual = state_hash(game);
+            if actual != expected {
+                game.stop_replay();
+                return Err(ReplayError::Diverged {
+                    frame,
+                    expected,
+                    actual,
+                });
+            }
+        }
+    }
+    game.stop_replay();
+    Ok(played)
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+    Diverged {
+        frame: usize,
+        expected: u64,
+        actual: u64,
+    },
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::Io(e) => write!(f, "Recording could not be accessed. {}", e),
+            ReplayError::Parse(e) => write!(f, "Recording is not valid. {}", e),
+            ReplayError::Diverged {
+                frame,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Replay diverged at frame {}. Expected state {:016x}, found {:016x}",
+                frame, expected, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{Action, BehaviourContext, BehaviourTree, Status};
+    use crate::engine::EngineConfig;
+    use crate::framework::{Actor, Bot, BotContext, UpdateContext};
+    use crate::input::{Action as InputAction, Key, ScriptedInput};
+    use crate::message::BotCommand;
+    use crate::physics::Transform;
+    use crate::render::Renderer;
+    use crate::scene::Properties;
+
+    struct Walker {
+        strength: u8,
+        transform: Transform,
+    }
+
+    impl Actor for Walker {
+        fn id(&self) -> u32 {
+            1
+        }
+        fn draw(&self, _renderer: &mut dyn Renderer) {}
+        fn update(&mut self, context: &mut UpdateContext) {
+            let step = context.random().range(0.0, 10.0);
+            if context.input().is_pressed(InputAction::MoveRight) {
+                self.transform.position.x += step;
+            }
+        }
+        fn on_command(&mut self, command: &BotCommand) {
+            if let BotCommand::ChangeStrength { amount, .. } = command {
+                self.strength = (self.strength as i16 + amount) as u8;
+            }
+        }
+        fn save(&self, properties: &mut Properties) {
+            properties.set("strength", self.strength);
+            properties.set_transform(&self.transform);
+        }
+    }
+
+    // Thread'de çalıştığı için komutlarının hangi frame'e denk geleceği belli değildir
+    struct Pusher;
+
+    impl Bot for Pusher {
+        fn apply(&self, context: &BotContext) {
+            context.send(BotCommand::ChangeStrength {
+                actor_id: 1,
+                amount: 2,
+            });
+        }
+    }
+
+    struct Doubler;
+
+    impl Bot for Doubler {
+        fn behaviour(&self) -> Option<BehaviourTree> {
+            Some(BehaviourTree::new(Box::new(Action::new(
+                |context: &mut BehaviourContext| {
+                    let strength = context.find_actor(1).map_or(0, |actor| actor.strength());
+                    context.send(BotCommand::ChangeStrength {
+                        actor_id: 1,
+                        amount: strength.min(5) as i16,
+                    });
+                    Status::Success
+                },
+            ))))
+        }
+    }
+
+    fn create_game() -> Game {
+        let mut game = Game::new();
+        game.add_actor(Box::new(Walker {
+            strength: 1,
+            transform: Transform::new(0.0, 0.0, 8.0, 8.0),
+        }));
+        game.add_bot(Box::new(Pusher));
+        game.add_bot(Box::new(Doubler));
+        game
+    }
+
+    fn record() -> Recording {
+        let mut game = create_game();
+        game.set_input_source(Box::new(ScriptedInput::new().hold(1, 4, Key::Right)));
+        game.start_recording(7);
+        game.apply();
+        let mut engine = GameEngine::new(EngineConfig::new(10, 60));
+        for elapsed in [100, 250, 80, 120, 300] {
+            engine.frame(&mut game, Duration::from_millis(elapsed));
+        }
+        game.shutdown();
+        game.stop_recording().unwrap()
+    }
+
+    #[test]
+    fn should_replay_recording_frame_for_frame_test() {
+        let recording = record();
+        assert_eq!(recording.frames.len(), 5);
+        let recording = Recording::parse(&recording.to_json().unwrap()).unwrap();
+
+        let mut game = create_game();
+        let mut engine = GameEngine::new(EngineConfig::new(10, 60));
+        let played = replay(
+            recording.clone(),
+            &mut game,
+            &mut engine,
+            ReplayMode::Verify,
+        )
+        .unwrap();
+        assert_eq!(played, 5);
+        assert_eq!(state_hash(&game), recording.frames.last().unwrap().state);
+    }
+
+    #[test]
+    fn should_fail_verification_when_replay_diverges_test() {
+        let mut recording = record();
+        recording.seed += 1;
+
+        let mut game = create_game();
+        let mut engine = GameEngine::new(EngineConfig::new(10, 60));
+        let result = replay(recording, &mut game, &mut engine, ReplayMode::Verify);
+        assert!(matches!(
+            result,
+            Err(ReplayError::Diverged { frame: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn should_hash_with_stable_fnv1a_test() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+}
@@ -0,0 +1,342 @@
+use crate::framework::Game;
+use crate::scene::{
+    ActorDefinition, BotDefinition, Properties, SceneDefinition, SceneError, SceneRegistry,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// Kayıt dosyası formatının güncel sürümü. Format değiştiğinde artırılmalı ve
+/// önceki sürüm için bir göç (migration) fonksiyonu eklenmelidir.
+pub const SAVE_VERSION: u32 = 1;
+
+/// Çalışan bir oyunun kaydedilmiş hali.
+///
+/// Aktörler ve bot'lar sahne tanımı ile aynı formatta saklanır, böylece geri yükleme işlemi
+/// sahne yüklerken kullanılan kayıt defteri (registry) üzerinden yapılır.
+/// Kanalda henüz işlenmemiş mesajlar ve sadece bileşenlerden oluşan entity'ler kaydedilmez.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveGame {
+    pub version: u32,
+    pub score: i64,
+    pub scene: SceneDefinition,
+}
+
+impl SaveGame {
+    pub fn capture(game: &Game, name: &str) -> Self {
+        let actors = game
+            .world()
+            .actors
+            .iter()
+            .map(|(entity, actor)| {
+                let mut properties = Properties::default();
+                actor.save(&mut properties);
+                // Tür etiketi aktör oluşturulurken tekrar verildiği için saklanmaz
+                let tags = game
+                    .world()
+                    .tags
+                    .get(entity)
+                    .into_iter()
+                    .flatten()
+                    .filter(|tag| tag.as_str() != actor.kind())
+                    .cloned()
+                    .collect();
+                ActorDefinition {
+                    kind: actor.kind().to_string(),
+                    id: actor.id(),
+                    tags,
+                    properties,
+                }
+            })
+            .collect();
+        let bots = game
+            .bots()
+            .iter()
+            .map(|container| {
+                let mut properties = Properties::default();
+                container.bot().save(&mut properties);
+                BotDefinition {
+                    kind: container.bot().kind().to_string(),
+                    active: container.is_active(),
+                    properties,
+                }
+            })
+            .collect();
+
+        SaveGame {
+            version: SAVE_VERSION,
+            score: game.score(),
+            scene: SceneDefinition {
+                name: name.to_string(),
+                actors,
+                bots,
+            },
+        }
+    }
+
+    /// Kaydı yeni bir oyun nesnesine yükler. Bot'lar `Game::apply` çağrılana kadar başlatılmaz.
+    pub fn restore(&self, registry: &SceneRegistry) -> Result<Game, SaveError> {
+        let mut game = Game::new();
+        registry
+            .load(&self.scene, &mut game)
+            .map_err(SaveError::Scene)?;
+        game.set_score(self.score);
+        Ok(game)
+    }
+
+    pub fn to_json(&self) -> Result<String, SaveError> {
+        serde_json::to_string_pretty(self).map_err(SaveError::Parse)
+    }
+
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), SaveError> {
+        std::fs::write(path, self.to_json()?).map_err(SaveError::Io)
+    }
+
+    /// Kaydı okur, eski sürümdeyse göç fonksiyonlarından geçirerek güncel sürüme taşır.
+    pub fn parse(content: &str, migrations: &Migrations) -> Result<Self, SaveError> {
+        let document: Value = serde_json::from_str(content).map_err(SaveError::Parse)?;
+        let document = migrations.migrate(document)?;
+        serde_json::from_value(document).map_err(SaveError::Parse)
+    }
+
+    pub fn read_from_file<P: AsRef<Path>>(
+        path: P,
+        migrations: &Migrations,
+    ) -> Result<Self, SaveError> {
+        let content = std::fs::read_to_string(path).map_err(SaveError::Io)?;
+        Self::parse(&content, migrations)
+    }
+}
+
+pub type Migration = Box<dyn Fn(Value) -> Result<Value, SaveError>>;
+
+/// Eski sürümdeki kayıtları bir sonraki sürüme taşıyan fonksiyonlar.
+#[derive(Default)]
+pub struct Migrations {
+    steps: HashMap<u32, Migration>,
+}
+
+impl Migrations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// `from_version` sürümündeki dokümanı bir sonraki sürüme taşıyan fonksiyonu ekler.
+    /// Fonksiyonun dokümandaki `version` alanını güncellemesine gerek yoktur.
+    pub fn register<F>(&mut self, from_version: u32, migration: F)
+    where
+        F: Fn(Value) -> Result<Value, SaveError> + 'static,
+    {
+        self.steps.insert(from_version, Box::new(migration));
+    }
+
+    pub fn migrate(&self, mut document: Value) -> Result<Value, SaveError> {
+        let mut version = document
+            .get("version")
+            .and_then(Value::as_u64)
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or(SaveError::MissingVersion)?;
+        if version > SAVE_VERSION {
+            return Err(SaveError::UnsupportedVersion(version));
+        }
+        while version < SAVE_VERSION {
+            let step = self
+                .steps
+                .get(&version)
+                .ok_or(SaveError::MissingMigration(version))?;
+            document = step(document)?;
+            version += 1;
+            document
+                .as_object_mut()
+                .ok_or_else(|| {
+                    SaveError::Migration(format!(
+                        "migration from version {} did not return an object",
+                        version - 1
+                    ))
+                })?
+                .insert("version".to_string(), Value::from(version));
+        }
+        Ok(document)
+    }
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+    MissingVersion,
+    UnsupportedVersion(u32),
+    MissingMigration(u32),
+    Migration(String),
+    Scene(SceneError),
+}
+
+impl Display for SaveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveError::Io(e) => write!(f, "Save file could not be accessed. {}", e),
+            SaveError::Parse(e) => write!(f, "Save file is not valid. {}", e),
+            SaveError::MissingVersion => write!(f, "Save file has no version"),
+            SaveError::UnsupportedVersion(v) => {
+                write!(
+                    f,
+                    "Save version {} is newer than supported {}",
+                    v, SAVE_VERSION
+                )
+            }
+            SaveError::MissingMigration(v) => write!(f, "No migration from save version {}", v),
+            SaveError::Migration(message) => write!(f, "Migration failed. {}", message),
+            SaveError::Scene(e) => write!(f, "Save could not be restored. {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{EngineConfig, GameEngine};
+    use crate::framework::{Actor, Bot, UpdateContext};
+    use crate::physics::Transform;
+    use crate::render::Renderer;
+    use std::time::Duration;
+
+    struct Runner {
+        id: u32,
+        name: String,
+        strength: u8,
+        transform: Transform,
+    }
+
+    impl Actor for Runner {
+        fn id(&self) -> u32 {
+            self.id
+        }
+        fn kind(&self) -> &'static str {
+            "runner"
+        }
+        fn draw(&self, _renderer: &mut dyn Renderer) {}
+        fn update(&mut self, context: &mut UpdateContext) {
+            self.transform.position.x += 10.0 * context.delta_time();
+            self.strength = self.strength.saturating_add(1);
+        }
+        fn strength(&self) -> u8 {
+            self.strength
+        }
+        fn transform(&self) -> Option<&Transform> {
+            Some(&self.transform)
+        }
+        fn save(&self, properties: &mut Properties) {
+            properties.set("name", self.name.as_str());
+            properties.set("strength", self.strength);
+            properties.set_transform(&self.transform);
+        }
+    }
+
+    struct Watcher {
+        target: u32,
+    }
+
+    impl Bot for Watcher {
+        fn kind(&self) -> &'static str {
+            "watcher"
+        }
+        fn save(&self, properties: &mut Properties) {
+            properties.set("target", self.target);
+        }
+    }
+
+    fn create_registry() -> SceneRegistry {
+        let mut registry = SceneRegistry::new();
+        registry.register_actor("runner", |definition| {
+            Ok(Box::new(Runner {
+                id: definition.id,
+                name: definition.properties.str("name")?.to_string(),
+                strength: definition.properties.or("strength", 0, Properties::u8)?,
+                transform: definition.properties.transform()?,
+            }))
+        });
+        registry.register_bot("watcher", |definition| {
+            Ok(Box::new(Watcher {
+                target: definition.properties.u32("target")?,
+            }))
+        });
+        registry
+    }
+
+    fn create_game() -> Game {
+        let mut game = Game::new();
+        let runner = game.add_actor(Box::new(Runner {
+            id: 1,
+            name: "Luigi".to_string(),
+            strength: 3,
+            transform: Transform::new(0.0, 10.0, 8.0, 8.0),
+        }));
+        game.world_mut().tag(runner, "hero");
+        game.add_bot_with_state(Box::new(Watcher { target: 1 }), false);
+        game.set_score(40);
+        game
+    }
+
+    #[test]
+    fn should_restore_state_saved_during_run_test() {
+        let mut game = create_game();
+        let mut engine = GameEngine::new(EngineConfig::new(10, 60));
+        engine.frame(&mut game, Duration::from_millis(500));
+
+        let saved = SaveGame::capture(&game, "Mid run");
+        let path =
+            std::env::temp_dir().join(format!("game_engine_save_{}.json", std::process::id()));
+        saved.write_to_file(&path).unwrap();
+        let loaded = SaveGame::read_from_file(&path, &Migrations::new()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, saved);
+
+        let mut restored = loaded.restore(&create_registry()).unwrap();
+        assert_eq!(SaveGame::capture(&restored, "Mid run"), saved);
+        assert_eq!(restored.score(), 40);
+        assert_eq!(saved.scene.actors[0].tags, vec!["hero".to_string()]);
+        assert!(!restored.bots()[0].is_active());
+        let runner = restored.world_mut().find_actor_mut(1).unwrap();
+        assert_eq!(runner.strength(), 8);
+        assert_eq!(runner.transform().unwrap().position.x, 5.0);
+    }
+
+    #[test]
+    fn should_migrate_older_versions_test() {
+        let old = r#"{
+            "version": 0,
+            "points": 12,
+            "scene": { "name": "Old" }
+        }"#;
+        assert!(matches!(
+            SaveGame::parse(old, &Migrations::new()),
+            Err(SaveError::MissingMigration(0))
+        ));
+
+        let mut migrations = Migrations::new();
+        migrations.register(0, |mut document| {
+            let points = document["points"].take();
+            document["score"] = points;
+            Ok(document)
+        });
+        let save = SaveGame::parse(old, &migrations).unwrap();
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.score, 12);
+
+        let newer = r#"{ "version": 99, "score": 0, "scene": { "name": "New" } }"#;
+        assert!(matches!(
+            SaveGame::parse(newer, &migrations),
+            Err(SaveError::UnsupportedVersion(99))
+        ));
+
+        let mut broken = Migrations::new();
+        broken.register(0, |_| Ok(Value::Null));
+        assert!(matches!(
+            SaveGame::parse(old, &broken),
+            Err(SaveError::Migration(_))
+        ));
+    }
+}
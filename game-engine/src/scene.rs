@@ -0,0 +1,367 @@
+use crate::framework::{Actor, Bot, Game};
+use crate::physics::Transform;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// Bir seviyede yer alan aktörleri ve bot'ları tanımlayan sahne bilgisi.
+///
+/// Sahneler JSON formatındaki dosyalardan okunur. Örnek bir dosya için `scenes/level_1.json`
+/// dosyasına bakılabilir.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub actors: Vec<ActorDefinition>,
+    #[serde(default)]
+    pub bots: Vec<BotDefinition>,
+}
+
+impl SceneDefinition {
+    pub fn parse(content: &str) -> Result<Self, SceneError> {
+        serde_json::from_str(content).map_err(SceneError::Parse)
+    }
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, SceneError> {
+        let content = std::fs::read_to_string(path).map_err(SceneError::Io)?;
+        Self::parse(&content)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActorDefinition {
+    pub kind: String,
+    pub id: u32,
+    /// Aktörün türüne ek olarak dahil olduğu gruplar
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub properties: Properties,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BotDefinition {
+    pub kind: String,
+    #[serde(default = "default_active")]
+    pub active: bool,
+    #[serde(default)]
+    pub properties: Properties,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+/// Aktör ve bot'lara ait isim-değer çiftleri.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Properties(BTreeMap<String, Value>);
+
+impl Properties {
+    pub fn set<V: Into<Value>>(&mut self, name: &str, value: V) {
+        self.0.insert(name.to_string(), value.into());
+    }
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.0.get(name)
+    }
+    fn required(&self, name: &str) -> Result<&Value, SceneError> {
+        self.get(name)
+            .ok_or_else(|| SceneError::MissingProperty(name.to_string()))
+    }
+    pub fn str(&self, name: &str) -> Result<&str, SceneError> {
+        self.required(name)?
+            .as_str()
+            .ok_or_else(|| SceneError::invalid(name, "string"))
+    }
+    pub fn f32(&self, name: &str) -> Result<f32, SceneError> {
+        self.required(name)?
+            .as_f64()
+            .map(|value| value as f32)
+            .ok_or_else(|| SceneError::invalid(name, "number"))
+    }
+    pub fn u32(&self, name: &str) -> Result<u32, SceneError> {
+        self.required(name)?
+            .as_u64()
+            .and_then(|value| u32::try_from(value).ok())
+            .ok_or_else(|| SceneError::invalid(name, "u32"))
+    }
+    pub fn u8(&self, name: &str) -> Result<u8, SceneError> {
+        self.required(name)?
+            .as_u64()
+            .and_then(|value| u8::try_from(value).ok())
+            .ok_or_else(|| SceneError::invalid(name, "u8"))
+    }
+    pub fn bool(&self, name: &str) -> Result<bool, SceneError> {
+        self.required(name)?
+            .as_bool()
+            .ok_or_else(|| SceneError::invalid(name, "bool"))
+    }
+    /// Özellik tanımlanmamışsa varsayılan değeri döner. Tanımlı ama geçersizse hata döner.
+    pub fn or<T, F>(&self, name: &str, default: T, read: F) -> Result<T, SceneError>
+    where
+        F: Fn(&Self, &str) -> Result<T, SceneError>,
+    {
+        match self.get(name) {
+            Some(_) => read(self, name),
+            None => Ok(default),
+        }
+    }
+    /// `x`, `y`, `width` ve `height` özelliklerinden konum bilgisini oluşturur.
+    pub fn transform(&self) -> Result<Transform, SceneError> {
+        Ok(Transform::new(
+            self.f32("x")?,
+            self.f32("y")?,
+            self.f32("width")?,
+            self.f32("height")?,
+        ))
+    }
+    pub fn set_transform(&mut self, transform: &Transform) {
+        self.set("x", transform.position.x);
+        self.set("y", transform.position.y);
+        self.set("width", transform.width);
+        self.set("height", transform.height);
+    }
+}
+
+#[derive(Debug)]
+pub enum SceneError {
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+    UnknownActorKind(String),
+    UnknownBotKind(String),
+    MissingProperty(String),
+    /// Aynı id değerine sahip birden fazla aktör var
+    DuplicateActorId(u32),
+    InvalidProperty {
+        name: String,
+        expected: &'static str,
+    },
+}
+
+impl SceneError {
+    fn invalid(name: &str, expected: &'static str) -> Self {
+        SceneError::InvalidProperty {
+            name: name.to_string(),
+            expected,
+        }
+    }
+}
+
+impl Display for SceneError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SceneError::Io(e) => write!(f, "Scene file could not be read. {}", e),
+            SceneError::Parse(e) => write!(f, "Scene file is not valid. {}", e),
+            SceneError::UnknownActorKind(kind) => write!(f, "Unknown actor kind '{}'", kind),
+            SceneError::UnknownBotKind(kind) => write!(f, "Unknown bot kind '{}'", kind),
+            SceneError::MissingProperty(name) => write!(f, "Property '{}' is missing", name),
+            SceneError::DuplicateActorId(id) => {
+                write!(f, "More than one actor has the id {}", id)
+            }
+            SceneError::InvalidProperty { name, expected } => {
+                write!(f, "Property '{}' must be a {}", name, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SceneError {}
+
+pub type ActorConstructor = Box<dyn Fn(&ActorDefinition) -> Result<Box<dyn Actor>, SceneError>>;
+pub type BotConstructor = Box<dyn Fn(&BotDefinition) -> Result<Box<dyn Bot>, SceneError>>;
+
+/// Sahnedeki tür isimlerini aktör ve bot oluşturan fonksiyonlarla eşleştirir.
+#[derive(Default)]
+pub struct SceneRegistry {
+    actors: HashMap<String, ActorConstructor>,
+    bots: HashMap<String, BotConstructor>,
+}
+
+impl SceneRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn register_actor<F>(&mut self, kind: &str, constructor: F)
+    where
+        F: Fn(&ActorDefinition) -> Result<Box<dyn Actor>, SceneError> + 'static,
+    {
+        self.actors.insert(kind.to_string(), Box::new(constructor));
+    }
+    pub fn register_bot<F>(&mut self, kind: &str, constructor: F)
+    where
+        F: Fn(&BotDefinition) -> Result<Box<dyn Bot>, SceneError> + 'static,
+    {
+        self.bots.insert(kind.to_string(), Box::new(constructor));
+    }
+    pub fn create_actor(&self, definition: &ActorDefinition) -> Result<Box<dyn Actor>, SceneError> {
+        let constructor = self
+            .actors
+            .get(&definition.kind)
+            .ok_or_else(|| SceneError::UnknownActorKind(definition.kind.clone()))?;
+        constructor(definition)
+    }
+    pub fn create_bot(&self, definition: &BotDefinition) -> Result<Box<dyn Bot>, SceneError> {
+        let constructor = self
+            .bots
+            .get(&definition.kind)
+            .ok_or_else(|| SceneError::UnknownBotKind(definition.kind.clone()))?;
+        constructor(definition)
+    }
+    /// Sahnedeki tüm nesneleri oluşturup oyuna ekler.
+    ///
+    /// Nesnelerden biri oluşturulamazsa veya aktör id değerleri birbiriyle ya da oyundaki
+    /// aktörlerle çakışıyorsa oyuna hiçbir şey eklenmez.
+    pub fn load(&self, scene: &SceneDefinition, game: &mut Game) -> Result<(), SceneError> {
+        let mut ids = HashSet::new();
+        for definition in &scene.actors {
+            if !ids.insert(definition.id) || game.world().find_actor(definition.id).is_some() {
+                return Err(SceneError::DuplicateActorId(definition.id));
+            }
+        }
+        let actors = scene
+            .actors
+            .iter()
+            .map(|definition| Ok((self.create_actor(definition)?, &definition.tags)))
+            .collect::<Result<Vec<_>, SceneError>>()?;
+        let bots = scene
+            .bots
+            .iter()
+            .map(|definition| Ok((self.create_bot(definition)?, definition.active)))
+            .collect::<Result<Vec<_>, SceneError>>()?;
+
+        for (actor, tags) in actors {
+            let entity = game.add_actor(actor);
+            for tag in tags {
+                game.world_mut().tag(entity, tag);
+            }
+        }
+        for (bot, is_active) in bots {
+            game.add_bot_with_state(bot, is_active);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::UpdateContext;
+    use crate::render::Renderer;
+
+    struct Coin {
+        id: u32,
+        value: u8,
+    }
+
+    impl Actor for Coin {
+        fn id(&self) -> u32 {
+            self.id
+        }
+        fn kind(&self) -> &'static str {
+            "coin"
+        }
+        fn draw(&self, _renderer: &mut dyn Renderer) {}
+        fn update(&mut self, _context: &mut UpdateContext) {}
+        fn strength(&self) -> u8 {
+            self.value
+        }
+    }
+
+    struct Idle;
+
+    impl Bot for Idle {}
+
+    fn create_registry() -> SceneRegistry {
+        let mut registry = SceneRegistry::new();
+        registry.register_actor("coin", |definition| {
+            Ok(Box::new(Coin {
+                id: definition.id,
+                value: definition.properties.u8("value")?,
+            }))
+        });
+        registry.register_bot("idle", |_| Ok(Box::new(Idle)));
+        registry
+    }
+
+    #[test]
+    fn should_load_actors_and_bots_from_scene_test() {
+        let scene = SceneDefinition::parse(
+            r#"{
+                "name": "Coins",
+                "actors": [
+                    { "kind": "coin", "id": 1, "properties": { "value": 5 } },
+                    { "kind": "coin", "id": 2, "tags": ["bonus"], "properties": { "value": 7 } }
+                ],
+                "bots": [ { "kind": "idle", "active": false } ]
+            }"#,
+        )
+        .unwrap();
+
+        let mut game = Game::new();
+        create_registry().load(&scene, &mut game).unwrap();
+
+        assert_eq!(game.world().actors.len(), 2);
+        assert_eq!(game.world_mut().find_actor_mut(2).unwrap().strength(), 7);
+        let bonus: Vec<_> = game.world().tagged("bonus").collect();
+        assert_eq!(bonus, vec![game.world().entity_of(2).unwrap()]);
+        assert_eq!(game.world().tagged("coin").count(), 2);
+        assert_eq!(game.bots().len(), 1);
+        assert!(!game.bots()[0].is_active());
+    }
+
+    #[test]
+    fn should_reject_unknown_kinds_and_bad_properties_test() {
+        let registry = create_registry();
+        let mut game = Game::new();
+
+        let unknown = SceneDefinition::parse(
+            r#"{ "name": "Bad", "actors": [ { "kind": "goomba", "id": 1 } ] }"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            registry.load(&unknown, &mut game),
+            Err(SceneError::UnknownActorKind(kind)) if kind == "goomba"
+        ));
+
+        let invalid = SceneDefinition::parse(
+            r#"{ "name": "Bad", "actors": [ { "kind": "coin", "id": 1, "properties": { "value": 300 } } ] }"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            registry.load(&invalid, &mut game),
+            Err(SceneError::InvalidProperty { .. })
+        ));
+        assert!(game.world().actors.is_empty());
+    }
+
+    #[test]
+    fn should_reject_duplicate_actor_ids_test() {
+        let registry = create_registry();
+        let mut game = Game::new();
+
+        let duplicate = SceneDefinition::parse(
+            r#"{ "name": "Bad", "actors": [
+                { "kind": "coin", "id": 2, "properties": { "value": 1 } },
+                { "kind": "coin", "id": 2, "properties": { "value": 2 } }
+            ] }"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            registry.load(&duplicate, &mut game),
+            Err(SceneError::DuplicateActorId(2))
+        ));
+        assert!(game.world().actors.is_empty());
+
+        let scene = SceneDefinition::parse(
+            r#"{ "name": "Coins", "actors": [ { "kind": "coin", "id": 2, "properties": { "value": 1 } } ] }"#,
+        )
+        .unwrap();
+        registry.load(&scene, &mut game).unwrap();
+        assert!(matches!(
+            registry.load(&scene, &mut game),
+            Err(SceneError::DuplicateActorId(2))
+        ));
+        assert_eq!(game.world().actors.len(), 1);
+    }
+}
@@ -0,0 +1,459 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    time::Duration,
+};
+
+use tokio::{
+    sync::{Semaphore, mpsc, oneshot},
+    task::JoinHandle,
+};
+
+use crate::{
+    handlers::RouteHandler,
+    metrics::{FALLBACK_ROUTE, Metrics, UNMATCHED, short_type_name},
+    request::Request,
+    router::RouteError,
+    routes::{PatternError, RoutePattern},
+};
+
+pub type HandlerFuture<'a> = Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send + 'a>>;
+
+/// `RouteHandler` trait'inin asenkron karşılığı.
+pub trait AsyncRouteHandler: Send + Sync {
+    fn handle<'a>(&'a self, request: &'a Request) -> HandlerFuture<'a>;
+    /// Metriklerde ve iz kayıtlarında kullanılan ad. Varsayılan olarak tipin adıdır.
+    fn name(&self) -> &str {
+        short_type_name(std::any::type_name::<Self>())
+    }
+}
+
+/// Senkron bir handler'ı bloklayan işler için ayrılmış thread'lerde çalıştırır.
+pub struct BlockingHandler<H> {
+    handler: Arc<H>,
+}
+
+impl<H: RouteHandler + Send + Sync + 'static> BlockingHandler<H> {
+    pub fn new(handler: H) -> Self {
+        BlockingHandler {
+            handler: Arc::new(handler),
+        }
+    }
+}
+
+impl<H: RouteHandler + Send + Sync + 'static> AsyncRouteHandler for BlockingHandler<H> {
+    fn handle<'a>(&'a self, request: &'a Request) -> HandlerFuture<'a> {
+        let handler = Arc::clone(&self.handler);
+        let request = request.clone();
+        Box::pin(
+            async move { tokio::task::spawn_blocking(move || handler.handle(&request)).await? },
+        )
+    }
+    /// Metriklerde sarmalanan handler'ın adı kullanılır
+    fn name(&self) -> &str {
+        self.handler.name()
+    }
+}
+
+struct AsyncRoute {
+    pattern: Option<RoutePattern>,
+    handler: Arc<dyn AsyncRouteHandler>,
+    timeout: Option<Duration>,
+}
+
+struct Job {
+    request: Request,
+    reply: oneshot::Sender<anyhow::Result<()>>,
+}
+
+/// İstekleri tokio üzerinde eş zamanlı işleyen router.
+///
+/// - En fazla `concurrency` istek aynı anda işlenir.
+/// - Kuyrukta en fazla `queue_capacity` istek bekleyebilir. Kuyruk doluysa `submit` yer
+///   açılana kadar bekler, `try_submit` ise hata döner.
+/// - Aynı yola gelen istekler geliş sırasına göre birbiri ardına işlenir. Farklı yollar
+///   birbirini beklemez.
+/// - Rotaya zaman aşımı verilmişse süreyi aşan istekler `AsyncError::Timeout` ile sonlanır.
+pub struct AsyncRouter {
+    routes: Vec<AsyncRoute>,
+    fallback: Option<AsyncRoute>,
+    metrics: Arc<Metrics>,
+    concurrency: usize,
+    queue_capacity: usize,
+}
+
+impl AsyncRouter {
+    pub fn new(concurrency: usize, queue_capacity: usize) -> Self {
+        AsyncRouter {
+            routes: Vec::new(),
+            fallback: None,
+            metrics: Arc::default(),
+            concurrency: concurrency.max(1),
+            queue_capacity: queue_capacity.max(1),
+        }
+    }
+    pub fn route(
+        &mut self,
+        pattern: &str,
+        handler: Arc<dyn AsyncRouteHandler>,
+        timeout: Option<Duration>,
+    ) -> Result<(), PatternError> {
+        self.routes.push(AsyncRoute {
+            pattern: Some(RoutePattern::parse(pattern)?),
+            handler,
+            timeout,
+        });
+        Ok(())
+    }
+    /// Rotası olmayan isteklerin gönderileceği handler
+    pub fn set_fallback(&mut self, handler: Arc<dyn AsyncRouteHandler>, timeout: Option<Duration>) {
+        self.fallback = Some(AsyncRoute {
+            pattern: None,
+            handler,
+            timeout,
+        });
+    }
+
+    pub fn metrics(&self) -> &Arc<Metrics> {
+        &self.metrics
+    }
+    /// Metriklerin başka bir router ile ortak tutulması için kullanılır
+    pub fn set_metrics(&mut self, metrics: Arc<Metrics>) {
+        self.metrics = metrics;
+    }
+
+    /// İstekleri işleyen görevi başlatır. Tüm `AsyncRouterHandle` nesneleri drop edildiğinde
+    /// kuyruktaki istekler işlenir ve görev sonlanır.
+    pub fn start(self) -> (AsyncRouterHandle, JoinHandle<()>) {
+        // Kanal tek isteklik tampondur, kuyruğun geri kalanı `run` içinde izin bekleyen
+        // isteklerden oluşur
+        let (sender, receiver) = mpsc::channel(1);
+        let task = tokio::spawn(self.run(receiver));
+        (AsyncRouterHandle { sender }, task)
+    }
+
+    async fn run(self, mut receiver: mpsc::Receiver<Job>) {
+        let router = Arc::new(self);
+        // Kabul edilen ama bitmemiş isteklerin sayısı `concurrency + queue_capacity` ile
+        // sınırlanır. Kanaldaki bir istek de bu sayıya dahil olduğundan izin, kuyruktan istek
+        // alınmadan önce alınır. İzin yoksa kanal dolu kalır ve `try_submit` hata döner
+        let in_flight_limit = router.concurrency + router.queue_capacity - 1;
+        let in_flight = Arc::new(Semaphore::new(in_flight_limit));
+        let running = Arc::new(Semaphore::new(router.concurrency));
+        // Her yol için o yoldaki son isteğin bitişini bildiren alıcı
+        let mut last_by_path: HashMap<String, oneshot::Receiver<()>> = HashMap::new();
+
+        loop {
+            let Ok(slot) = Arc::clone(&in_flight).acquire_owned().await else {
+                break;
+            };
+            let Some(job) = receiver.recv().await else {
+                break;
+            };
+            let (done, finished) = oneshot::channel();
+            let previous = last_by_path.insert(job.request.path.clone(), finished);
+            if last_by_path.len() > in_flight_limit {
+                last_by_path.retain(|_, receiver| {
+                    matches!(
+                        receiver.try_recv(),
+                        Err(oneshot::error::TryRecvError::Empty)
+                    )
+                });
+            }
+
+            let router = Arc::clone(&router);
+            let running = Arc::clone(&running);
+            tokio::spawn(async move {
+                // Önceki istek beklenirken çalışma izni tutulmaz, diğer yollar işlenmeye devam eder
+                if let Some(previous) = previous {
+                    let _ = previous.await;
+                }
+                let result = match running.acquire().await {
+                    Ok(_permit) => router.dispatch(&job.request).await,
+                    Err(_) => Err(AsyncError::Closed.into()),
+                };
+                drop(slot);
+                let _ = done.send(());
+                let _ = job.reply.send(result);
+            });
+        }
+        // Çalışan isteklerin bitmesi beklenir
+        let _ = in_flight.acquire_many(in_flight_limit as u32).await;
+    }
+
+    async fn dispatch(&self, request: &Request) -> anyhow::Result<()> {
+        let matched = self.routes.iter().find_map(|route| {
+            let params = route.pattern.as_ref()?.matches(&request.path)?;
+            Some((route, params))
+        });
+        let (route, request) = match matched {
+            Some((route, params)) => {
+                let mut request = request.clone();
+                request.params = params;
+                (route, request)
+            }
+            None => match &self.fallback {
+                Some(route) => (route, request.clone()),
+                None => {
+                    let span = self.metrics.start(request, UNMATCHED, UNMATCHED);
+                    let result = Err(RouteError::NotFound(request.path.clone()).into());
+                    span.finish(&result);
+                    return result;
+                }
+            },
+        };
+        let name = route
+            .pattern
+            .as_ref()
+            .map_or(FALLBACK_ROUTE, |p| p.as_str());
+        let span = self.metrics.start(&request, name, route.handler.name());
+        let result = match route.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, route.handler.handle(&request))
+                .await
+                .unwrap_or_else(|_| {
+                    Err(AsyncError::Timeout {
+                        path: request.path.clone(),
+                        timeout,
+                    }
+                    .into())
+                }),
+            None => route.handler.handle(&request).await,
+        };
+        span.finish(&result);
+        result
+    }
+}
+
+/// Çalışan `AsyncRouter` görevine istek göndermek için kullanılır. Klonlanabilir.
+#[derive(Clone)]
+pub struct AsyncRouterHandle {
+    sender: mpsc::Sender<Job>,
+}
+
+impl AsyncRouterHandle {
+    /// İsteği kuyruğa ekler, kuyruk doluysa yer açılmasını bekler. Dönen alıcı isteğin
+    /// sonucunu verir.
+    pub async fn submit(
+        &self,
+        request: Request,
+    ) -> Result<oneshot::Receiver<anyhow::Result<()>>, AsyncError> {
+        let (reply, result) = oneshot::channel();
+        self.sender
+            .send(Job { request, reply })
+            .await
+            .map_err(|_| AsyncError::Closed)?;
+        Ok(result)
+    }
+    /// İsteği beklemeden kuyruğa eklemeyi dener. Kuyruk doluysa `QueueFull` döner.
+    pub fn try_submit(
+        &self,
+        request: Request,
+    ) -> Result<oneshot::Receiver<anyhow::Result<()>>, AsyncError> {
+        let (reply, result) = oneshot::channel();
+        self.sender
+            .try_send(Job { request, reply })
+            .map_err(|e| match e {
+                mpsc::error::TrySendError::Full(_) => AsyncError::QueueFull,
+                mpsc::error::TrySendError::Closed(_) => AsyncError::Closed,
+            })?;
+        Ok(result)
+    }
+    /// İsteği kuyruğa ekler ve işlenmesini bekler.
+    pub async fn dispatch(&self, request: Request) -> anyhow::Result<()> {
+        self.submit(request)
+            .await?
+            .await
+            .map_err(|_| AsyncError::Closed)?
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AsyncError {
+    QueueFull,
+    Closed,
+    Timeout { path: String, timeout: Duration },
+}
+
+impl Display for AsyncError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AsyncError::QueueFull => write!(f, "Request queue is full"),
+            AsyncError::Closed => write!(f, "Router is not running"),
+            AsyncError::Timeout { path, timeout } => {
+                write!(f, "Handling '{}' timed out after {:?}", path, timeout)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AsyncError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::sync::Notify;
+
+    /// İstekleri gövdelerinde yazan milisaniye kadar bekleterek işler
+    #[derive(Default)]
+    struct Sleeper {
+        active: AtomicUsize,
+        max_active: AtomicUsize,
+        finished: Mutex<Vec<String>>,
+    }
+
+    impl AsyncRouteHandler for Sleeper {
+        fn handle<'a>(&'a self, request: &'a Request) -> HandlerFuture<'a> {
+            Box::pin(async move {
+                let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+                self.max_active.fetch_max(active, Ordering::SeqCst);
+                let millis: u64 = String::from_utf8_lossy(&request.data).parse()?;
+                tokio::time::sleep(Duration::from_millis(millis)).await;
+                self.active.fetch_sub(1, Ordering::SeqCst);
+                self.finished
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} {}", request.path, millis));
+                Ok(())
+            })
+        }
+    }
+
+    struct Blocked {
+        release: Arc<Notify>,
+    }
+
+    impl AsyncRouteHandler for Blocked {
+        fn handle<'a>(&'a self, _request: &'a Request) -> HandlerFuture<'a> {
+            Box::pin(async move {
+                self.release.notified().await;
+                Ok(())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn should_limit_concurrency_and_keep_order_per_path_test() {
+        let sleeper = Arc::new(Sleeper::default());
+        let mut router = AsyncRouter::new(2, 8);
+        router.set_fallback(sleeper.clone(), None);
+        let (handle, task) = router.start();
+
+        let mut results = Vec::new();
+        for (path, millis) in [
+            ("a", "60"),
+            ("a", "1"),
+            ("b", "30"),
+            ("c", "5"),
+            ("a", "10"),
+        ] {
+            results.push(handle.submit(Request::new(path, millis)).await.unwrap());
+        }
+        for result in results {
+            result.await.unwrap().unwrap();
+        }
+        drop(handle);
+        task.await.unwrap();
+
+        assert_eq!(sleeper.max_active.load(Ordering::SeqCst), 2);
+        let finished = sleeper.finished.lock().unwrap().clone();
+        let a: Vec<_> = finished.iter().filter(|f| f.starts_with('a')).collect();
+        assert_eq!(a, vec!["a 60", "a 1", "a 10"]);
+        // b, a yolundaki uzun isteği beklemeden biter
+        assert_eq!(finished[0], "b 30");
+    }
+
+    #[tokio::test]
+    async fn should_time_out_slow_handlers_test() {
+        let mut router = AsyncRouter::new(1, 1);
+        router
+            .route(
+                "slow",
+                Arc::new(Sleeper::default()),
+                Some(Duration::from_millis(20)),
+            )
+            .unwrap();
+        let (handle, _task) = router.start();
+
+        let error = handle
+            .dispatch(Request::new("slow", "500"))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<AsyncError>(),
+            Some(AsyncError::Timeout { path, .. }) if path == "slow"
+        ));
+        let error = handle
+            .dispatch(Request::new("other", "1"))
+            .await
+            .unwrap_err();
+        assert!(error.downcast_ref::<RouteError>().is_some());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_apply_back_pressure_when_queue_is_full_test() {
+        let release = Arc::new(Notify::new());
+        let mut router = AsyncRouter::new(1, 1);
+        router.set_fallback(
+            Arc::new(Blocked {
+                release: Arc::clone(&release),
+            }),
+            None,
+        );
+        let (handle, _task) = router.start();
+
+        // İlk istek işlenmeye başlar, ikincisi kuyrukta kalır. İkinci istek kanala
+        // girebildiyse ilki kanaldan alınmış demektir
+        let first = handle.submit(Request::new("a", "")).await.unwrap();
+        let second = handle.submit(Request::new("b", "")).await.unwrap();
+        for _ in 0..3 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            assert!(matches!(
+                handle.try_submit(Request::new("c", "")),
+                Err(AsyncError::QueueFull)
+            ));
+        }
+
+        for result in [first, second] {
+            release.notify_one();
+            result.await.unwrap().unwrap();
+        }
+    }
+
+    struct Named(&'static str);
+
+    impl RouteHandler for Named {
+        fn handle(&self, _request: &Request) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn name(&self) -> &str {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn should_report_wrapped_handler_names_in_metrics_test() {
+        let files = BlockingHandler::new(crate::handlers::FileStorageHandler::new("unused.log"));
+        assert_eq!(files.name(), "FileStorageHandler");
+
+        let mut router = AsyncRouter::new(1, 1);
+        router
+            .route("a", Arc::new(BlockingHandler::new(Named("A"))), None)
+            .unwrap();
+        router
+            .route("b", Arc::new(BlockingHandler::new(Named("B"))), None)
+            .unwrap();
+        let metrics = Arc::clone(router.metrics());
+        let (handle, _task) = router.start();
+        handle.dispatch(Request::new("a", "")).await.unwrap();
+        handle.dispatch(Request::new("b", "")).await.unwrap();
+
+        assert_eq!(metrics.handler_counters("A").succeeded, 1);
+        assert_eq!(metrics.handler_counters("B").succeeded, 1);
+        assert_eq!(metrics.handler_counters("BlockingHandler").received, 0);
+    }
+}
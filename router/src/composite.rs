@@ -0,0 +1,252 @@
+use std::fmt::{Display, Formatter};
+
+use crate::{handlers::RouteHandler, request::Request, routes::split};
+
+/// Dağıtımın ne zaman başarılı sayılacağını belirler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FanOutPolicy {
+    /// Tüm handler'lar başarılı olmalı
+    All,
+    /// En az bir handler başarılı olmalı
+    Any,
+}
+
+/// İsteği tüm handler'lara sırayla iletir.
+///
+/// Bir handler'ın hata alması diğerlerine iletimi engellemez. Sonuç politikaya göre belirlenir.
+pub struct FanOutHandler {
+    pub handlers: Vec<Box<dyn RouteHandler>>,
+    pub policy: FanOutPolicy,
+}
+
+impl FanOutHandler {
+    pub fn new(policy: FanOutPolicy, handlers: Vec<Box<dyn RouteHandler>>) -> Self {
+        FanOutHandler { handlers, policy }
+    }
+}
+
+impl RouteHandler for FanOutHandler {
+    fn handle(&self, request: &Request) -> anyhow::Result<()> {
+        let errors: Vec<String> = self
+            .handlers
+            .iter()
+            .filter_map(|handler| handler.handle(request).err())
+            .map(|e| format!("{:#}", e))
+            .collect();
+        let is_success = match self.policy {
+            FanOutPolicy::All => errors.is_empty(),
+            FanOutPolicy::Any => errors.len() < self.handlers.len(),
+        };
+        if is_success {
+            return Ok(());
+        }
+        Err(CompositeError::FanOutFailed {
+            total: self.handlers.len(),
+            errors,
+        }
+        .into())
+    }
+}
+
+/// `ConditionalHandler` dallarının seçilme koşulları.
+pub enum Condition {
+    PathPrefix(String),
+    PayloadContains(Vec<u8>),
+    Custom(Box<dyn Fn(&Request) -> bool>),
+}
+
+impl Condition {
+    pub fn matches(&self, request: &Request) -> bool {
+        match self {
+            // Rotalarda olduğu gibi yol parçaları karşılaştırılır, `api` önekine `apiary` uymaz
+            Condition::PathPrefix(prefix) => split(&request.path).starts_with(&split(prefix)),
+            Condition::PayloadContains(needle) => {
+                needle.is_empty()
+                    || request
+                        .data
+                        .windows(needle.len())
+                        .any(|window| window == needle.as_slice())
+            }
+            Condition::Custom(check) => check(request),
+        }
+    }
+}
+
+/// İsteği koşulu sağlanan ilk dala iletir. Hiçbir koşul sağlanmazsa `otherwise` handler'ı
+/// kullanılır.
+#[derive(Default)]
+pub struct ConditionalHandler {
+    branches: Vec<(Condition, Box<dyn RouteHandler>)>,
+    otherwise: Option<Box<dyn RouteHandler>>,
+}
+
+impl ConditionalHandler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn when(mut self, condition: Condition, handler: Box<dyn RouteHandler>) -> Self {
+        self.branches.push((condition, handler));
+        self
+    }
+    pub fn otherwise(mut self, handler: Box<dyn RouteHandler>) -> Self {
+        self.otherwise = Some(handler);
+        self
+    }
+}
+
+impl RouteHandler for ConditionalHandler {
+    fn handle(&self, request: &Request) -> anyhow::Result<()> {
+        let handler = self
+            .branches
+            .iter()
+            .find(|(condition, _)| condition.matches(request))
+            .map(|(_, handler)| handler)
+            .or(self.otherwise.as_ref())
+            .ok_or_else(|| CompositeError::NoMatchingBranch(request.path.clone()))?;
+        handler.handle(request)
+    }
+}
+
+/// Birincil handler hata alırsa isteği yedek handler'a iletir.
+pub struct FallbackHandler {
+    pub primary: Box<dyn RouteHandler>,
+    pub fallback: Box<dyn RouteHandler>,
+}
+
+impl RouteHandler for FallbackHandler {
+    fn handle(&self, request: &Request) -> anyhow::Result<()> {
+        match self.primary.handle(request) {
+            Ok(()) => Ok(()),
+            Err(primary) => self
+                .fallback
+                .handle(request)
+                .map_err(|e| e.context(format!("Primary handler failed. {:#}", primary))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompositeError {
+    FanOutFailed { total: usize, errors: Vec<String> },
+    NoMatchingBranch(String),
+}
+
+impl Display for CompositeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompositeError::FanOutFailed { total, errors } => write!(
+                f,
+                "{} of {} handlers failed. {}",
+                errors.len(),
+                total,
+                errors.join("; ")
+            ),
+            CompositeError::NoMatchingBranch(path) => {
+                write!(f, "No condition matched '{}'", path)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CompositeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Calls = Rc<RefCell<Vec<&'static str>>>;
+
+    struct Named {
+        name: &'static str,
+        fails: bool,
+        calls: Calls,
+    }
+
+    impl RouteHandler for Named {
+        fn handle(&self, _request: &Request) -> anyhow::Result<()> {
+            self.calls.borrow_mut().push(self.name);
+            if self.fails {
+                anyhow::bail!("{} failed", self.name);
+            }
+            Ok(())
+        }
+    }
+
+    fn named(name: &'static str, fails: bool, calls: &Calls) -> Box<dyn RouteHandler> {
+        Box::new(Named {
+            name,
+            fails,
+            calls: Rc::clone(calls),
+        })
+    }
+
+    #[test]
+    fn should_fan_out_with_all_and_any_policies_test() {
+        let calls = Calls::default();
+        let request = Request::new("api/orders", "{}");
+        let handlers = || vec![named("a", true, &calls), named("b", false, &calls)];
+
+        let all = FanOutHandler::new(FanOutPolicy::All, handlers());
+        let error = all.handle(&request).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<CompositeError>(),
+            Some(&CompositeError::FanOutFailed {
+                total: 2,
+                errors: vec!["a failed".to_string()]
+            })
+        );
+        assert_eq!(*calls.borrow(), vec!["a", "b"]);
+
+        let any = FanOutHandler::new(FanOutPolicy::Any, handlers());
+        assert!(any.handle(&request).is_ok());
+        let none = FanOutHandler::new(FanOutPolicy::Any, vec![named("c", true, &calls)]);
+        assert!(none.handle(&request).is_err());
+    }
+
+    #[test]
+    fn should_choose_branch_by_condition_and_fall_back_test() {
+        let calls = Calls::default();
+        let handler = ConditionalHandler::new()
+            .when(
+                Condition::PathPrefix("api/users".into()),
+                named("users", false, &calls),
+            )
+            .when(
+                Condition::PayloadContains(b"\"priority\":true".to_vec()),
+                Box::new(FallbackHandler {
+                    primary: named("fast", true, &calls),
+                    fallback: named("slow", false, &calls),
+                }),
+            );
+
+        handler.handle(&Request::new("/api/users/1", "")).unwrap();
+        handler
+            .handle(&Request::new("api/orders", "{\"priority\":true}"))
+            .unwrap();
+        assert_eq!(*calls.borrow(), vec!["users", "fast", "slow"]);
+
+        let error = handler
+            .handle(&Request::new("api/orders", "{}"))
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<CompositeError>(),
+            Some(&CompositeError::NoMatchingBranch("api/orders".to_string()))
+        );
+        let handler = handler.otherwise(named("rest", false, &calls));
+        handler.handle(&Request::new("api/orders", "{}")).unwrap();
+        assert_eq!(calls.borrow().last(), Some(&"rest"));
+    }
+
+    #[test]
+    fn should_match_path_prefix_by_segments_test() {
+        let condition = Condition::PathPrefix("/api".into());
+        assert!(condition.matches(&Request::new("api", "")));
+        assert!(condition.matches(&Request::new("/api/users/1", "")));
+        assert!(condition.matches(&Request::new("api//users", "")));
+        assert!(!condition.matches(&Request::new("/apiary", "")));
+        assert!(!condition.matches(&Request::new("v1/api", "")));
+        assert!(Condition::PathPrefix("".into()).matches(&Request::new("anything", "")));
+    }
+}
@@ -0,0 +1,603 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use serde::Deserialize;
+
+use crate::{
+    composite::{Condition, ConditionalHandler, FallbackHandler, FanOutHandler, FanOutPolicy},
+    handlers::{FileStorageHandler, PassToRemoteHandler, RouteHandler},
+    middleware::{
+        AuthMiddleware, LoggingMiddleware, Middleware, SizeLimitMiddleware, TimingMiddleware,
+    },
+    router::Router,
+    routes::{PatternError, RoutePattern},
+};
+
+/// Uzak handler'lar için dosyada verilebilecek en fazla yeniden deneme sayısı
+pub const MAX_RETRIES: u32 = 10;
+
+/// Router'ın rotalarını, handler'larını ve ara katmanlarını tanımlayan TOML dosyası.
+///
+/// ```toml
+/// default = "storage"
+///
+/// [[middlewares]]
+/// type = "size_limit"
+/// max_bytes = 1048576
+///
+/// [handlers.storage]
+/// type = "file"
+/// file = "route_datas.log"
+/// rotate_bytes = 1048576
+/// max_files = 5
+///
+/// [handlers.products]
+/// type = "remote"
+/// uri = "https://backend-services/api/products/"
+/// timeout_ms = 2000
+///
+/// [[routes]]
+/// path = "api/products/**"
+/// handler = "products"
+/// ```
+///
+/// Handler'lar adları ile tanımlanır. Rotalar ve birleşik handler'lar (`fan_out`,
+/// `conditional`, `fallback`) diğer handler'lara bu adlar ile başvurur.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RouterConfig {
+    /// Rotası olmayan isteklerin gönderileceği handler
+    pub default: Option<String>,
+    #[serde(default)]
+    pub middlewares: Vec<MiddlewareConfig>,
+    #[serde(default)]
+    pub handlers: BTreeMap<String, HandlerConfig>,
+    #[serde(default)]
+    pub routes: Vec<RouteConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum MiddlewareConfig {
+    Logging,
+    Timing,
+    SizeLimit { max_bytes: usize },
+    Auth { header: String, tokens: Vec<String> },
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum HandlerConfig {
+    File {
+        file: String,
+        rotate_bytes: Option<u64>,
+        #[serde(default)]
+        max_files: usize,
+    },
+    Remote {
+        uri: String,
+        timeout_ms: Option<u64>,
+        retries: Option<u32>,
+        backoff_ms: Option<u64>,
+    },
+    FanOut {
+        policy: PolicyConfig,
+        handlers: Vec<String>,
+    },
+    Conditional {
+        branches: Vec<BranchConfig>,
+        otherwise: Option<String>,
+    },
+    Fallback {
+        primary: String,
+        fallback: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyConfig {
+    All,
+    Any,
+}
+
+/// `conditional` handler'ının bir dalı. Koşul olarak `path_prefix` veya `contains`
+/// alanlarından sadece biri verilmelidir.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BranchConfig {
+    pub path_prefix: Option<String>,
+    pub contains: Option<String>,
+    pub handler: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RouteConfig {
+    pub path: String,
+    pub handler: String,
+}
+
+impl RouterConfig {
+    pub fn parse(content: &str) -> Result<Self, ConfigError> {
+        let config: RouterConfig = toml::from_str(content).map_err(ConfigError::Parse)?;
+        config.validate()?;
+        Ok(config)
+    }
+    pub fn read_from_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let content = std::fs::read_to_string(path).map_err(ConfigError::Io)?;
+        Self::parse(&content)
+    }
+
+    /// Tüm başvuruların tanımlı handler'lara ait olduğunu, handler'ların birbirine döngüsel
+    /// olarak başvurmadığını ve alanların geçerli olduğunu kontrol eder.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if let Some(name) = &self.default {
+            self.check_reference("default", name)?;
+        }
+        for middleware in &self.middlewares {
+            match middleware {
+                MiddlewareConfig::SizeLimit { max_bytes: 0 } => {
+                    return Err(invalid(
+                        "size_limit middleware",
+                        "max_bytes must be positive",
+                    ));
+                }
+                MiddlewareConfig::Auth { header, tokens } => {
+                    if header.trim().is_empty() {
+                        return Err(invalid("auth middleware", "header must not be empty"));
+                    }
+                    if tokens.is_empty() {
+                        return Err(invalid("auth middleware", "at least one token is required"));
+                    }
+                }
+                _ => {}
+            }
+        }
+        for (name, handler) in &self.handlers {
+            self.validate_handler(name, handler)?;
+        }
+        for route in &self.routes {
+            RoutePattern::parse(&route.path).map_err(|error| ConfigError::InvalidRoute {
+                path: route.path.clone(),
+                error,
+            })?;
+            self.check_reference(&format!("route '{}'", route.path), &route.handler)?;
+        }
+        for name in self.handlers.keys() {
+            self.check_cycle(name, &mut Vec::new())?;
+        }
+        Ok(())
+    }
+
+    fn validate_handler(&self, name: &str, handler: &HandlerConfig) -> Result<(), ConfigError> {
+        let owner = format!("handler '{}'", name);
+        match handler {
+            HandlerConfig::File {
+                file,
+                rotate_bytes,
+                max_files,
+            } => {
+                if file.trim().is_empty() {
+                    return Err(invalid(&owner, "file must not be empty"));
+                }
+                if *rotate_bytes == Some(0) {
+                    return Err(invalid(&owner, "rotate_bytes must be positive"));
+                }
+                if rotate_bytes.is_none() && *max_files > 0 {
+                    return Err(invalid(&owner, "max_files requires rotate_bytes"));
+                }
+            }
+            HandlerConfig::Remote {
+                uri,
+                timeout_ms,
+                retries,
+                ..
+            } => {
+                if !uri.starts_with("http://") && !uri.starts_with("https://") {
+                    return Err(invalid(&owner, "uri must start with http:// or https://"));
+                }
+                if *timeout_ms == Some(0) {
+                    return Err(invalid(&owner, "timeout_ms must be positive"));
+                }
+                if retries.is_some_and(|retries| retries > MAX_RETRIES) {
+                    return Err(invalid(
+                        &owner,
+                        &format!("retries must be at most {}", MAX_RETRIES),
+                    ));
+                }
+            }
+            HandlerConfig::FanOut { handlers, .. } => {
+                if handlers.is_empty() {
+                    return Err(invalid(&owner, "at least one handler is required"));
+                }
+                for handler in handlers {
+                    self.check_reference(&owner, handler)?;
+                }
+            }
+            HandlerConfig::Conditional {
+                branches,
+                otherwise,
+            } => {
+                for branch in branches {
+                    if branch.path_prefix.is_some() == branch.contains.is_some() {
+                        return Err(invalid(
+                            &owner,
+                            "each branch needs exactly one of path_prefix or contains",
+                        ));
+                    }
+                    self.check_reference(&owner, &branch.handler)?;
+                }
+                if let Some(otherwise) = otherwise {
+                    self.check_reference(&owner, otherwise)?;
+                }
+            }
+            HandlerConfig::Fallback { primary, fallback } => {
+                self.check_reference(&owner, primary)?;
+                self.check_reference(&owner, fallback)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_reference(&self, owner: &str, name: &str) -> Result<(), ConfigError> {
+        if self.handlers.contains_key(name) {
+            return Ok(());
+        }
+        Err(ConfigError::UnknownHandler {
+            owner: owner.to_string(),
+            name: name.to_string(),
+        })
+    }
+
+    fn check_cycle(&self, name: &str, path: &mut Vec<String>) -> Result<(), ConfigError> {
+        if path.iter().any(|visited| visited == name) {
+            path.push(name.to_string());
+            return Err(ConfigError::Cycle(path.clone()));
+        }
+        path.push(name.to_string());
+        if let Some(handler) = self.handlers.get(name) {
+            for reference in references(handler) {
+                self.check_cycle(reference, path)?;
+            }
+        }
+        path.pop();
+        Ok(())
+    }
+
+    /// Tanımlardan yeni bir router oluşturur.
+    pub fn build(&self) -> Result<Router, ConfigError> {
+        self.validate()?;
+        let mut router = Router::default();
+        if let Some(name) = &self.default {
+            router.handler = Some(self.build_handler(name));
+        }
+        for middleware in &self.middlewares {
+            router.add_middleware(build_middleware(middleware));
+        }
+        for route in &self.routes {
+            router
+                .route(&route.path, self.build_handler(&route.handler))
+                .map_err(|error| ConfigError::InvalidRoute {
+                    path: route.path.clone(),
+                    error,
+                })?;
+        }
+        Ok(router)
+    }
+
+    // Tanımlar doğrulandıktan sonra çağrılır. Birden fazla yerden başvurulan handler'lar için
+    // ayrı nesneler oluşturulur
+    fn build_handler(&self, name: &str) -> Box<dyn RouteHandler> {
+        match &self.handlers[name] {
+            HandlerConfig::File {
+                file,
+                rotate_bytes,
+                max_files,
+            } => {
+                let handler = FileStorageHandler::new(file);
+                match rotate_bytes {
+                    Some(max_bytes) => Box::new(handler.with_rotation(*max_bytes, *max_files)),
+                    None => Box::new(handler),
+                }
+            }
+            HandlerConfig::Remote {
+                uri,
+                timeout_ms,
+                retries,
+                backoff_ms,
+            } => {
+                let mut handler = PassToRemoteHandler::new(uri);
+                if let Some(timeout) = timeout_ms {
+                    handler = handler.with_timeout(Duration::from_millis(*timeout));
+                }
+                let retries = retries.unwrap_or(handler.retries);
+                let backoff = backoff_ms.map_or(handler.backoff, Duration::from_millis);
+                Box::new(handler.with_retries(retries, backoff))
+            }
+            HandlerConfig::FanOut { policy, handlers } => {
+                let policy = match policy {
+                    PolicyConfig::All => FanOutPolicy::All,
+                    PolicyConfig::Any => FanOutPolicy::Any,
+                };
+                let handlers = handlers.iter().map(|h| self.build_handler(h)).collect();
+                Box::new(FanOutHandler::new(policy, handlers))
+            }
+            HandlerConfig::Conditional {
+                branches,
+                otherwise,
+            } => {
+                let mut handler = ConditionalHandler::new();
+                for branch in branches {
+                    let condition = match (&branch.path_prefix, &branch.contains) {
+                        (Some(prefix), _) => Condition::PathPrefix(prefix.clone()),
+                        (_, contains) => {
+                            Condition::PayloadContains(contains.clone().unwrap_or_default().into())
+                        }
+                    };
+                    handler = handler.when(condition, self.build_handler(&branch.handler));
+                }
+                if let Some(otherwise) = otherwise {
+                    handler = handler.otherwise(self.build_handler(otherwise));
+                }
+                Box::new(handler)
+            }
+            HandlerConfig::Fallback { primary, fallback } => Box::new(FallbackHandler {
+                primary: self.build_handler(primary),
+                fallback: self.build_handler(fallback),
+            }),
+        }
+    }
+}
+
+fn references(handler: &HandlerConfig) -> Vec<&str> {
+    match handler {
+        HandlerConfig::File { .. } | HandlerConfig::Remote { .. } => Vec::new(),
+        HandlerConfig::FanOut { handlers, .. } => handlers.iter().map(String::as_str).collect(),
+        HandlerConfig::Conditional {
+            branches,
+            otherwise,
+        } => branches
+            .iter()
+            .map(|branch| branch.handler.as_str())
+            .chain(otherwise.as_deref())
+            .collect(),
+        HandlerConfig::Fallback { primary, fallback } => vec![primary, fallback],
+    }
+}
+
+fn build_middleware(middleware: &MiddlewareConfig) -> Box<dyn Middleware> {
+    match middleware {
+        MiddlewareConfig::Logging => Box::new(LoggingMiddleware),
+        MiddlewareConfig::Timing => Box::new(TimingMiddleware),
+        MiddlewareConfig::SizeLimit { max_bytes } => Box::new(SizeLimitMiddleware {
+            max_bytes: *max_bytes,
+        }),
+        MiddlewareConfig::Auth { header, tokens } => {
+            let tokens: Vec<&str> = tokens.iter().map(String::as_str).collect();
+            Box::new(AuthMiddleware::new(header, &tokens))
+        }
+    }
+}
+
+fn invalid(owner: &str, message: &str) -> ConfigError {
+    ConfigError::Invalid {
+        owner: owner.to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// Yapılandırma dosyasının değişip değişmediğini değiştirilme zamanına bakarak izler.
+pub struct ConfigWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl ConfigWatcher {
+    /// Dosyayı okuyarak ilk router'ı oluşturur. Dosyadaki hatalar başlangıçta döner.
+    pub fn open(path: impl Into<PathBuf>) -> Result<(Self, Router), ConfigError> {
+        let mut watcher = ConfigWatcher {
+            path: path.into(),
+            modified: None,
+        };
+        let router = watcher.load()?;
+        Ok((watcher, router))
+    }
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Dosya son okumadan sonra değiştiyse yeniden okur ve yeni router'ı döner.
+    ///
+    /// Hatalı bir değişiklik sonrasında dosya tekrar değişene kadar aynı hata tekrar dönmez.
+    pub fn poll(&mut self) -> Option<Result<Router, ConfigError>> {
+        let modified = modified_time(&self.path).ok();
+        if modified.is_none() || modified == self.modified {
+            return None;
+        }
+        Some(self.load())
+    }
+
+    fn load(&mut self) -> Result<Router, ConfigError> {
+        self.modified = Some(modified_time(&self.path).map_err(ConfigError::Io)?);
+        RouterConfig::read_from_file(&self.path)?.build()
+    }
+}
+
+fn modified_time(path: &Path) -> std::io::Result<SystemTime> {
+    std::fs::metadata(path)?.modified()
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(std::io::Error),
+    Parse(toml::de::Error),
+    UnknownHandler { owner: String, name: String },
+    Cycle(Vec<String>),
+    InvalidRoute { path: String, error: PatternError },
+    Invalid { owner: String, message: String },
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "Configuration could not be read. {}", e),
+            ConfigError::Parse(e) => write!(f, "Configuration is not valid. {}", e),
+            ConfigError::UnknownHandler { owner, name } => {
+                write!(f, "{} refers to unknown handler '{}'", owner, name)
+            }
+            ConfigError::Cycle(path) => {
+                write!(f, "Handlers refer to each other: {}", path.join(" -> "))
+            }
+            ConfigError::InvalidRoute { path, error } => {
+                write!(f, "Route '{}' is not valid. {}", path, error)
+            }
+            ConfigError::Invalid { owner, message } => write!(f, "{}: {}", owner, message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::Request;
+    use crate::storage::RecordReader;
+
+    const CONFIG: &str = r#"
+default = "fallback"
+
+[[middlewares]]
+type = "size_limit"
+max_bytes = 16
+
+[handlers.fallback]
+type = "file"
+file = "FILE"
+
+[handlers.remote]
+type = "remote"
+uri = "http://127.0.0.1:9/api"
+timeout_ms = 200
+retries = 0
+
+[handlers.orders]
+type = "fan_out"
+policy = "any"
+handlers = ["fallback", "remote"]
+
+[[routes]]
+path = "api/orders/**"
+handler = "orders"
+"#;
+
+    fn error(content: &str) -> String {
+        RouterConfig::parse(content).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn should_build_router_from_config_test() {
+        let file =
+            std::env::temp_dir().join(format!("router_config_build_{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&file);
+        let config = CONFIG.replace("FILE", file.to_str().unwrap());
+        let router = RouterConfig::parse(&config).unwrap().build().unwrap();
+
+        router
+            .dispatch(&Request::new("api/orders/1", "{}"))
+            .unwrap();
+        router.dispatch(&Request::new("health", "")).unwrap();
+        assert!(
+            router
+                .dispatch(&Request::new("health", "far too long payload"))
+                .is_err()
+        );
+
+        let paths: Vec<String> = RecordReader::open(&file)
+            .unwrap()
+            .map(|record| record.unwrap().path)
+            .collect();
+        assert_eq!(paths, vec!["api/orders/1", "health"]);
+        assert_eq!(
+            router.metrics().route_counters("api/orders/**").succeeded,
+            1
+        );
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn should_report_invalid_config_clearly_test() {
+        assert_eq!(
+            error("[[routes]]\npath = \"api\"\nhandler = \"missing\"\n"),
+            "route 'api' refers to unknown handler 'missing'"
+        );
+        assert_eq!(
+            error("[handlers.a]\ntype = \"remote\"\nuri = \"ftp://host\"\n"),
+            "handler 'a': uri must start with http:// or https://"
+        );
+        assert_eq!(
+            error("[handlers.a]\ntype = \"remote\"\nuri = \"http://host\"\nretries = 4294967295\n"),
+            "handler 'a': retries must be at most 10"
+        );
+        assert_eq!(
+            error(
+                "[handlers.a]\ntype = \"fallback\"\nprimary = \"b\"\nfallback = \"b\"\n\
+                 [handlers.b]\ntype = \"fan_out\"\npolicy = \"all\"\nhandlers = [\"a\"]\n"
+            ),
+            "Handlers refer to each other: a -> b -> a"
+        );
+        assert!(
+            error("[handlers.a]\ntype = \"file\"\nfile = \"x\"\n[[routes]]\npath = \"**/a\"\nhandler = \"a\"\n")
+                .starts_with("Route '**/a' is not valid.")
+        );
+        assert!(error("[handlers.a]\ntype = \"disk\"\n").contains("unknown variant `disk`"));
+        assert!(
+            error("[handlers.a]\ntype = \"file\"\nfiel = \"x\"\n").contains("unknown field `fiel`")
+        );
+    }
+
+    #[test]
+    fn should_reload_router_when_file_changes_test() {
+        let path =
+            std::env::temp_dir().join(format!("router_config_reload_{}.toml", std::process::id()));
+        let routes = "[handlers.a]\ntype = \"file\"\nfile = \"a.log\"\n\
+                      [[routes]]\npath = \"a\"\nhandler = \"a\"\n";
+        std::fs::write(&path, routes).unwrap();
+
+        let (mut watcher, router) = ConfigWatcher::open(&path).unwrap();
+        assert!(router.dispatch(&Request::new("b", "")).is_err());
+        assert!(watcher.poll().is_none());
+
+        // Değiştirilme zamanı saniye hassasiyetinde olabilir
+        let later = SystemTime::now() + Duration::from_secs(2);
+        std::fs::write(&path, "default = \"missing\"\n").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert!(matches!(
+            watcher.poll(),
+            Some(Err(ConfigError::UnknownHandler { .. }))
+        ));
+        assert!(watcher.poll().is_none());
+
+        std::fs::write(&path, format!("default = \"a\"\n{routes}")).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later + Duration::from_secs(2))
+            .unwrap();
+        let router = watcher.poll().unwrap().unwrap();
+        assert!(router.handler.is_some());
+        assert!(watcher.poll().is_none());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
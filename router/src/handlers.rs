@@ -1,39 +1,341 @@
-use std::{fs::OpenOptions, io::Write};
+use std::{
+    fmt::{Display, Formatter},
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
+    thread,
+    time::Duration,
+};
 
-use crate::request::Request;
+use crate::{metrics::short_type_name, request::Request, storage::StoredRecord};
 
 pub trait RouteHandler {
     fn handle(&self, request: &Request) -> anyhow::Result<()>;
+    /// Metriklerde ve iz kayıtlarında kullanılan ad. Varsayılan olarak tipin adıdır.
+    fn name(&self) -> &str {
+        short_type_name(std::any::type_name::<Self>())
+    }
 }
 
+/// İstekleri `storage` modülündeki kayıt formatında dosyaya ekler.
+///
+/// `max_bytes` verilmişse dosya bu boyutu aşacağı zaman `dosya.1`, `dosya.2` şeklinde
+/// döndürülür (rotation). En fazla `max_files` eski dosya saklanır.
 pub struct FileStorageHandler {
     pub file_name: String,
+    pub max_bytes: Option<u64>,
+    pub max_files: usize,
+}
+
+impl FileStorageHandler {
+    pub fn new(file_name: impl Into<String>) -> Self {
+        FileStorageHandler {
+            file_name: file_name.into(),
+            max_bytes: None,
+            max_files: 0,
+        }
+    }
+    pub fn with_rotation(mut self, max_bytes: u64, max_files: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self.max_files = max_files;
+        self
+    }
+    pub fn rotated_name(&self, index: usize) -> String {
+        format!("{}.{}", self.file_name, index)
+    }
+
+    fn rotate_if_needed(&self, incoming: u64) -> std::io::Result<()> {
+        let Some(max_bytes) = self.max_bytes else {
+            return Ok(());
+        };
+        let size = match fs::metadata(&self.file_name) {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        // Tek başına sınırı aşan kayıtlar boş dosyaya yazılır
+        if size == 0 || size + incoming <= max_bytes {
+            return Ok(());
+        }
+        if self.max_files == 0 {
+            return fs::remove_file(&self.file_name);
+        }
+        for index in (1..self.max_files).rev() {
+            let from = self.rotated_name(index);
+            if Path::new(&from).exists() {
+                fs::rename(&from, self.rotated_name(index + 1))?;
+            }
+        }
+        fs::rename(&self.file_name, self.rotated_name(1))
+    }
 }
 
 impl RouteHandler for FileStorageHandler {
     fn handle(&self, request: &Request) -> anyhow::Result<()> {
+        let record = StoredRecord::from_request(request).encode()?;
+        self.rotate_if_needed(record.len() as u64)?;
         let mut file = OpenOptions::new()
             .append(true)
             .create(true)
             .open(self.file_name.clone())?;
-        file.write_all(&request.data)?;
+        file.write_all(&record)?;
         Ok(())
     }
 }
 
+/// İsteği `target_uri` ve istek yolunun birleşimi olan adrese POST eder.
+///
+/// Bağlantı hataları, zaman aşımları ve 5xx/429 cevapları tekrar denenir. Her denemede bekleme
+/// süresi iki katına çıkar. Diğer 4xx cevapları tekrar denenmeden hata olarak döner.
 pub struct PassToRemoteHandler {
     pub target_uri: String,
+    pub timeout: Duration,
+    pub retries: u32,
+    pub backoff: Duration,
+}
+
+/// Yeniden denemeler arasında beklenebilecek en uzun süre
+pub const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+impl PassToRemoteHandler {
+    pub fn new(target_uri: impl Into<String>) -> Self {
+        PassToRemoteHandler {
+            target_uri: target_uri.into(),
+            timeout: Duration::from_secs(10),
+            retries: 2,
+            backoff: Duration::from_millis(200),
+        }
+    }
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+    pub fn with_retries(mut self, retries: u32, backoff: Duration) -> Self {
+        self.retries = retries;
+        self.backoff = backoff;
+        self
+    }
+    /// `attempt` numaralı denemeden sonra beklenecek süre. Her denemede iki katına çıkar,
+    /// `MAX_BACKOFF` değerini geçmez.
+    pub fn retry_delay(&self, attempt: u32) -> Duration {
+        2u32.checked_pow(attempt)
+            .and_then(|factor| self.backoff.checked_mul(factor))
+            .map_or(MAX_BACKOFF, |delay| delay.min(MAX_BACKOFF))
+    }
+    pub fn url(&self, path: &str) -> String {
+        format!(
+            "{}/{}",
+            self.target_uri.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        )
+    }
+
+    fn post(&self, agent: &ureq::Agent, url: &str, request: &Request) -> Result<(), RemoteError> {
+        let content_type = request
+            .header("content-type")
+            .unwrap_or("application/octet-stream");
+        match agent
+            .post(url)
+            .set("Content-Type", content_type)
+            .send_bytes(&request.data)
+        {
+            Ok(_) => Ok(()),
+            Err(ureq::Error::Status(status, _)) => Err(RemoteError::Status {
+                url: url.to_string(),
+                status,
+            }),
+            Err(ureq::Error::Transport(e)) => Err(RemoteError::Transport {
+                url: url.to_string(),
+                message: e.to_string(),
+            }),
+        }
+    }
 }
 
 impl RouteHandler for PassToRemoteHandler {
     fn handle(&self, request: &Request) -> anyhow::Result<()> {
-        println!(
-            "Pretending to POST to {}{} with {} bytes",
-            self.target_uri,
-            request.path,
-            request.data.len()
+        let agent = ureq::AgentBuilder::new().timeout(self.timeout).build();
+        let url = self.url(&request.path);
+        let mut attempt = 0;
+        loop {
+            match self.post(&agent, &url, request) {
+                Err(e) if e.is_retryable() && attempt < self.retries => {
+                    thread::sleep(self.retry_delay(attempt));
+                    attempt += 1;
+                }
+                result => return Ok(result?),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RemoteError {
+    Status { url: String, status: u16 },
+    Transport { url: String, message: String },
+}
+
+impl RemoteError {
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RemoteError::Status { status, .. } => *status >= 500 || *status == 429,
+            RemoteError::Transport { .. } => true,
+        }
+    }
+}
+
+impl Display for RemoteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RemoteError::Status { url, status } => {
+                write!(f, "{} responded with status {}", url, status)
+            }
+            RemoteError::Transport { url, message } => {
+                write!(f, "{} could not be reached. {}", url, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RemoteError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read};
+    use std::net::TcpListener;
+    use std::sync::mpsc::{Receiver, channel};
+
+    /// Sıradaki her bağlantıya listedeki durum kodu ile cevap veren test sunucusu.
+    /// Gelen isteklerin yolu ve gövdesi kanala yazılır.
+    fn serve(statuses: Vec<u16>) -> (String, Receiver<(String, Vec<u8>)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = channel();
+        thread::spawn(move || {
+            for status in statuses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':')
+                        && name.eq_ignore_ascii_case("content-length")
+                    {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                let path = request_line.split_whitespace().nth(1).unwrap().to_string();
+                sender.send((path, body)).unwrap();
+                let response = format!(
+                    "HTTP/1.1 {} Test\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (address, receiver)
+    }
+
+    #[test]
+    fn should_append_records_and_rotate_by_size_test() {
+        let directory = std::env::temp_dir().join(format!("router_storage_{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let file_name = directory.join("routes.log").to_string_lossy().to_string();
+        let record_size = StoredRecord::from_request(&Request::new("a/1", "0123456789"))
+            .encode()
+            .unwrap()
+            .len() as u64;
+        let handler = FileStorageHandler::new(file_name.clone()).with_rotation(record_size * 2, 2);
+
+        for index in 0..7 {
+            let request = Request::new(format!("a/{}", index), "0123456789")
+                .with_header("Content-Type", "text/plain");
+            handler.handle(&request).unwrap();
+        }
+
+        let paths = |name: &str| -> Vec<String> {
+            crate::storage::RecordReader::open(name)
+                .unwrap()
+                .map(|record| record.unwrap().path)
+                .collect()
+        };
+        assert_eq!(paths(&file_name), vec!["a/6"]);
+        assert_eq!(paths(&handler.rotated_name(1)), vec!["a/4", "a/5"]);
+        assert_eq!(paths(&handler.rotated_name(2)), vec!["a/2", "a/3"]);
+        assert!(!Path::new(&handler.rotated_name(3)).exists());
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn should_post_data_and_retry_server_errors_test() {
+        let (address, received) = serve(vec![503, 500, 201]);
+        let handler = PassToRemoteHandler::new(format!("{}/api/", address))
+            .with_retries(2, Duration::from_millis(1));
+
+        handler
+            .handle(&Request::new("/users/1", b"{\"name\":\"jane\"}".to_vec()))
+            .unwrap();
+
+        let requests: Vec<_> = received.try_iter().collect();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[2].0, "/api/users/1");
+        assert_eq!(requests[2].1, b"{\"name\":\"jane\"}");
+    }
+
+    #[test]
+    fn should_map_status_to_errors_without_retrying_client_errors_test() {
+        let (address, received) = serve(vec![404, 200]);
+        let handler =
+            PassToRemoteHandler::new(address.clone()).with_retries(3, Duration::from_millis(1));
+
+        let error = handler.handle(&Request::new("missing", "")).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<RemoteError>(),
+            Some(&RemoteError::Status {
+                url: format!("{}/missing", address),
+                status: 404
+            })
         );
+        assert_eq!(received.try_iter().count(), 1);
+    }
 
-        Ok(())
+    #[test]
+    fn should_time_out_when_remote_does_not_respond_test() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let handler = PassToRemoteHandler::new(address)
+            .with_timeout(Duration::from_millis(100))
+            .with_retries(1, Duration::from_millis(1));
+
+        let error = handler.handle(&Request::new("slow", "")).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<RemoteError>(),
+            Some(RemoteError::Transport { .. })
+        ));
+        drop(listener);
+    }
+
+    #[test]
+    fn should_cap_retry_delay_for_large_retry_counts_test() {
+        let handler = PassToRemoteHandler::new("http://localhost")
+            .with_retries(u32::MAX, Duration::from_millis(200));
+        assert_eq!(handler.retry_delay(0), Duration::from_millis(200));
+        assert_eq!(handler.retry_delay(3), Duration::from_millis(1600));
+        for attempt in [8, 31, 32, 100, u32::MAX] {
+            assert_eq!(handler.retry_delay(attempt), MAX_BACKOFF);
+        }
+
+        let handler =
+            PassToRemoteHandler::new("http://localhost").with_retries(1000, Duration::MAX);
+        assert_eq!(handler.retry_delay(1), MAX_BACKOFF);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,11 @@
+pub mod async_router;
+pub mod composite;
+pub mod config;
+pub mod handlers;
+pub mod metrics;
+pub mod middleware;
+pub mod request;
+pub mod router;
+pub mod routes;
+pub mod server;
+pub mod storage;
@@ -1,37 +1,123 @@
-mod handlers;
-mod request;
-mod router;
+use std::time::Duration;
 
-use handlers::*;
-use request::Request;
-use router::Router;
+use router::handlers::*;
+use router::middleware::*;
+use router::request::Request;
+use router::router::{Delivery, Router};
+use router::server::HttpServer;
+use router::storage::RecordReader;
 
 fn main() -> anyhow::Result<()> {
+    // Kullanım: router --listen 127.0.0.1:8080 [--config router.toml] veya
+    // router --dump route_datas.log
+    // Sunucu metrikleri GET /metrics yolundan okunabilir
+    let args: Vec<String> = std::env::args().collect();
+    match (args.get(1).map(String::as_str), args.get(2)) {
+        (Some("--listen"), Some(address)) => {
+            let config = match (args.get(3).map(String::as_str), args.get(4)) {
+                (Some("--config"), Some(config)) => config,
+                _ => "router.toml",
+            };
+            return listen(address, config);
+        }
+        (Some("--dump"), Some(file_name)) => return dump(file_name),
+        _ => {}
+    }
+
     // File Storage kullanımı
 
-    let fs_handler = Box::new(FileStorageHandler {
-        file_name: "route_datas.log".into(),
-    });
+    let fs_handler =
+        Box::new(FileStorageHandler::new("route_datas.log").with_rotation(1024 * 1024, 5));
 
     let mut router = Router::new(fs_handler);
+    router.add_middleware(Box::new(LoggingMiddleware));
+    router.add_middleware(Box::new(TimingMiddleware));
+    router.add_middleware(Box::new(SizeLimitMiddleware { max_bytes: 1024 }));
+
+    router.add(Request::new(
+        "api/users/title",
+        b"{\"name\": \"john@doe\"}".to_vec(),
+    ));
+    report(&router.apply());
+
+    // İşlenen istekler kuyruktan çıkarıldığı için tekrar yazılmaz
+    router.add(Request::new(
+        "api/products",
+        b"{\"category\": \"Books\"}".to_vec(),
+    ));
+    report(&router.apply());
 
-    router.add(Request {
-        path: "api/users/title".into(),
-        data: b"{\"name\": \"john@doe\"}".to_vec(),
-    });
-    router.apply()?;
+    // Api Yönlendirme Kullanımı. Uzak servise ulaşılamazsa istek dead-letter kuyruğuna alınır
+    router.handler = Some(Box::new(
+        PassToRemoteHandler::new("https://backend-services/api/route/one")
+            .with_timeout(Duration::from_secs(2))
+            .with_retries(0, Duration::ZERO),
+    ));
+    router.add(Request::new("api/orders", b"{\"order\": 1}".to_vec()));
+    report(&router.apply());
 
-    router.add(Request {
-        path: "api/products".into(),
-        data: b"{\"category\": \"Books\"}".to_vec(),
-    });
-    router.apply()?;
+    // Dead-letter kuyruğundaki istekler varsayılan handler'a tekrar gönderilir
+    router.handler = Some(Box::new(
+        FileStorageHandler::new("route_datas.log").with_rotation(1024 * 1024, 5),
+    ));
+    report(&router.retry_dead_letters());
 
-    // Api Yönlendirme Kullanımı
-    router.handler = Box::new(PassToRemoteHandler {
-        target_uri: "https://backend-services/api/route/one".into(),
-    });
-    router.apply()?;
+    // Yola göre yönlendirme. Eşleşmeyen istekler varsayılan handler'a gider
+    router.route(
+        "api/users/{id}",
+        Box::new(FileStorageHandler::new("users.log")),
+    )?;
+    router.route(
+        "api/products/**",
+        Box::new(
+            PassToRemoteHandler::new("https://backend-services/api/products/")
+                .with_timeout(Duration::from_secs(2))
+                .with_retries(1, Duration::from_millis(100)),
+        ),
+    )?;
+    router.dispatch(&Request::new(
+        "api/users/42",
+        b"{\"name\": \"jane@doe\"}".to_vec(),
+    ))?;
+    if let Err(e) = router.dispatch(&Request::new("api/products/books/1", b"{}".to_vec())) {
+        eprintln!("Error: {}", e);
+    }
+
+    for trace in router.metrics().traces() {
+        println!("{}", trace);
+    }
+    Ok(())
+}
+
+// Depolama dosyasındaki kayıtları listeler
+fn dump(file_name: &str) -> anyhow::Result<()> {
+    for record in RecordReader::open(file_name)? {
+        let record = record?;
+        println!(
+            "{} {} {} {}",
+            record.timestamp,
+            record.path,
+            record.content_type,
+            String::from_utf8_lossy(&record.data)
+        );
+    }
+    Ok(())
+}
+
+fn report(deliveries: &[Delivery]) {
+    for delivery in deliveries {
+        match &delivery.result {
+            Ok(_) => println!("{} delivered", delivery.path),
+            Err(e) => eprintln!("{} moved to dead letters. {}", delivery.path, e),
+        }
+    }
+}
 
+// Gelen POST istekleri yapılandırma dosyasındaki rotalara göre dosyaya yazılır veya uzak
+// servise iletilir
+fn listen(address: &str, config: &str) -> anyhow::Result<()> {
+    let mut server = HttpServer::bind_with_config(address, config)?;
+    println!("Listening on {} with {}", server.local_addr()?, config);
+    server.serve()?;
     Ok(())
 }
@@ -0,0 +1,386 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::{Display, Formatter, Write},
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{request::Request, router::status_for};
+
+/// Hiçbir rotaya uymayıp varsayılan handler'a giden istekler için kullanılan rota adı
+pub const FALLBACK_ROUTE: &str = "<fallback>";
+/// Hiçbir handler'a ulaşamayan istekler için kullanılan rota ve handler adı
+pub const UNMATCHED: &str = "<none>";
+
+/// Gecikme histogramının saniye cinsinden üst sınırları
+pub const LATENCY_BUCKETS: [f64; 11] = [
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Counters {
+    pub received: u64,
+    pub succeeded: u64,
+    pub failed: u64,
+}
+
+impl Counters {
+    fn add(&mut self, other: &Counters) {
+        self.received += other.received;
+        self.succeeded += other.succeeded;
+        self.failed += other.failed;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// Her sınır için o sınıra eşit veya altındaki gözlem sayısı (kümülatif değil)
+    pub buckets: [u64; LATENCY_BUCKETS.len()],
+    pub count: u64,
+    pub sum: Duration,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Histogram {
+            buckets: [0; LATENCY_BUCKETS.len()],
+            count: 0,
+            sum: Duration::ZERO,
+        }
+    }
+}
+
+impl Histogram {
+    pub fn observe(&mut self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        if let Some(index) = LATENCY_BUCKETS.iter().position(|le| seconds <= *le) {
+            self.buckets[index] += 1;
+        }
+        self.count += 1;
+        self.sum += duration;
+    }
+    /// Her sınır için o sınırın altında kalan toplam gözlem sayısı
+    pub fn cumulative(&self) -> impl Iterator<Item = (f64, u64)> + '_ {
+        LATENCY_BUCKETS
+            .iter()
+            .zip(self.buckets.iter())
+            .scan(0, |total, (le, count)| {
+                *total += count;
+                Some((*le, *total))
+            })
+    }
+}
+
+/// Bir rota ve handler ikilisi için toplanan değerler.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteStats {
+    pub route: String,
+    pub handler: String,
+    pub counters: Counters,
+    pub latency: Histogram,
+}
+
+/// İşlenen her istek için tutulan kayıt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceRecord {
+    pub id: u64,
+    pub path: String,
+    pub route: String,
+    pub handler: String,
+    /// İsteğin işlenmeye başladığı an, unix epoch'tan itibaren milisaniye
+    pub timestamp: u64,
+    pub duration: Duration,
+    /// Sonucun karşılık geldiği HTTP durum kodu. Bkz. `router::status_for`
+    pub status: u16,
+    pub error: Option<String>,
+}
+
+impl Display for TraceRecord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "id={} ts={} path={:?} route={:?} handler={:?} status={} duration_ms={:.3}",
+            self.id,
+            self.timestamp,
+            self.path,
+            self.route,
+            self.handler,
+            self.status,
+            self.duration.as_secs_f64() * 1000.0
+        )?;
+        if let Some(error) = &self.error {
+            write!(f, " error={:?}", error)?;
+        }
+        Ok(())
+    }
+}
+
+type CounterField = fn(&Counters) -> u64;
+
+#[derive(Default)]
+struct State {
+    next_id: u64,
+    stats: BTreeMap<(String, String), RouteStats>,
+    traces: VecDeque<TraceRecord>,
+}
+
+/// Router'ların işlediği istekler için sayaç, gecikme histogramı ve iz kayıtlarını toplar.
+///
+/// Thread'ler arasında paylaşılabilir. İz kayıtlarından en fazla `max_traces` tanesi
+/// saklanır, yenileri geldikçe en eskileri silinir.
+pub struct Metrics {
+    state: Mutex<State>,
+    max_traces: usize,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new(1000)
+    }
+}
+
+impl Metrics {
+    pub fn new(max_traces: usize) -> Self {
+        Metrics {
+            state: Mutex::new(State::default()),
+            max_traces,
+        }
+    }
+
+    /// İsteğin alındığını kaydeder. Sonuç dönen `Span` ile bildirilir.
+    pub fn start(&self, request: &Request, route: &str, handler: &str) -> Span<'_> {
+        let mut state = self.state.lock().unwrap();
+        state.next_id += 1;
+        let id = state.next_id;
+        entry(&mut state, route, handler).counters.received += 1;
+        Span {
+            metrics: self,
+            id,
+            path: request.path.clone(),
+            route: route.to_string(),
+            handler: handler.to_string(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_millis() as u64),
+            started: Instant::now(),
+        }
+    }
+
+    fn finish(&self, span: Span<'_>, result: &anyhow::Result<()>) {
+        let duration = span.started.elapsed();
+        let mut state = self.state.lock().unwrap();
+        let stats = entry(&mut state, &span.route, &span.handler);
+        match result {
+            Ok(_) => stats.counters.succeeded += 1,
+            Err(_) => stats.counters.failed += 1,
+        }
+        stats.latency.observe(duration);
+
+        if self.max_traces == 0 {
+            return;
+        }
+        if state.traces.len() == self.max_traces {
+            state.traces.pop_front();
+        }
+        state.traces.push_back(TraceRecord {
+            id: span.id,
+            path: span.path,
+            route: span.route,
+            handler: span.handler,
+            timestamp: span.timestamp,
+            duration,
+            status: status_for(result),
+            error: result.as_ref().err().map(|e| format!("{:#}", e)),
+        });
+    }
+
+    /// Rota ve handler ikililerine göre sıralı istatistikler
+    pub fn stats(&self) -> Vec<RouteStats> {
+        self.state.lock().unwrap().stats.values().cloned().collect()
+    }
+    /// Verilen rotadaki tüm handler'ların toplam sayaçları
+    pub fn route_counters(&self, route: &str) -> Counters {
+        self.sum_counters(|stats| stats.route == route)
+    }
+    /// Verilen handler'ın tüm rotalardaki toplam sayaçları
+    pub fn handler_counters(&self, handler: &str) -> Counters {
+        self.sum_counters(|stats| stats.handler == handler)
+    }
+    /// Saklanan iz kayıtları, eskiden yeniye
+    pub fn traces(&self) -> Vec<TraceRecord> {
+        self.state.lock().unwrap().traces.iter().cloned().collect()
+    }
+
+    fn sum_counters(&self, filter: impl Fn(&RouteStats) -> bool) -> Counters {
+        let state = self.state.lock().unwrap();
+        let mut counters = Counters::default();
+        for stats in state.stats.values().filter(|stats| filter(stats)) {
+            counters.add(&stats.counters);
+        }
+        counters
+    }
+
+    /// Değerleri Prometheus metin formatında yazar.
+    pub fn render_prometheus(&self) -> String {
+        let stats = self.stats();
+        let mut out = String::new();
+        let counters: [(&str, &str, CounterField); 3] = [
+            ("received", "Requests received", |c| c.received),
+            ("succeeded", "Requests handled successfully", |c| {
+                c.succeeded
+            }),
+            ("failed", "Requests that failed", |c| c.failed),
+        ];
+        for (name, help, value) in counters {
+            let _ = writeln!(out, "# HELP router_requests_{name}_total {help}.");
+            let _ = writeln!(out, "# TYPE router_requests_{name}_total counter");
+            for stats in &stats {
+                let _ = writeln!(
+                    out,
+                    "router_requests_{name}_total{{{}}} {}",
+                    labels(stats),
+                    value(&stats.counters)
+                );
+            }
+        }
+
+        let name = "router_request_duration_seconds";
+        let _ = writeln!(out, "# HELP {name} Time spent handling requests.");
+        let _ = writeln!(out, "# TYPE {name} histogram");
+        for stats in &stats {
+            let labels = labels(stats);
+            for (le, count) in stats.latency.cumulative() {
+                let _ = writeln!(out, "{name}_bucket{{{labels},le=\"{le}\"}} {count}");
+            }
+            let count = stats.latency.count;
+            let _ = writeln!(out, "{name}_bucket{{{labels},le=\"+Inf\"}} {count}");
+            let sum = stats.latency.sum.as_secs_f64();
+            let _ = writeln!(out, "{name}_sum{{{labels}}} {sum}");
+            let _ = writeln!(out, "{name}_count{{{labels}}} {count}");
+        }
+        out
+    }
+}
+
+fn entry<'a>(state: &'a mut State, route: &str, handler: &str) -> &'a mut RouteStats {
+    state
+        .stats
+        .entry((route.to_string(), handler.to_string()))
+        .or_insert_with(|| RouteStats {
+            route: route.to_string(),
+            handler: handler.to_string(),
+            counters: Counters::default(),
+            latency: Histogram::default(),
+        })
+}
+
+fn labels(stats: &RouteStats) -> String {
+    format!(
+        "route=\"{}\",handler=\"{}\"",
+        escape(&stats.route),
+        escape(&stats.handler)
+    )
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// İşlenmekte olan bir isteğin ölçümü. Sonuç `finish` ile kaydedilir.
+pub struct Span<'a> {
+    metrics: &'a Metrics,
+    id: u64,
+    path: String,
+    route: String,
+    handler: String,
+    timestamp: u64,
+    started: Instant,
+}
+
+impl Span<'_> {
+    pub fn finish(self, result: &anyhow::Result<()>) {
+        let metrics = self.metrics;
+        metrics.finish(self, result);
+    }
+}
+
+/// `std::any::type_name` değerinden modül yolunu atarak tipin kısa adını döner.
+pub(crate) fn short_type_name(name: &'static str) -> &'static str {
+    let base = name.split('<').next().unwrap_or(name);
+    base.rsplit("::").next().unwrap_or(base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::RouteError;
+
+    #[test]
+    fn should_count_requests_and_keep_latest_traces_test() {
+        let metrics = Metrics::new(2);
+        let request = Request::new("api/users/7", "");
+        metrics
+            .start(&request, "api/users/{id}", "Users")
+            .finish(&Ok(()));
+        metrics
+            .start(&request, "api/users/{id}", "Users")
+            .finish(&Err(anyhow::anyhow!("disk is full")));
+        let request = Request::new("health", "");
+        metrics
+            .start(&request, UNMATCHED, UNMATCHED)
+            .finish(&Err(RouteError::NotFound("health".to_string()).into()));
+
+        assert_eq!(
+            metrics.route_counters("api/users/{id}"),
+            Counters {
+                received: 2,
+                succeeded: 1,
+                failed: 1
+            }
+        );
+        assert_eq!(metrics.handler_counters(UNMATCHED).failed, 1);
+        assert_eq!(metrics.stats()[1].latency.count, 2);
+
+        let traces = metrics.traces();
+        assert_eq!(traces.len(), 2);
+        assert_eq!(traces[0].id, 2);
+        assert_eq!(traces[0].status, 500);
+        assert_eq!(traces[0].error.as_deref(), Some("disk is full"));
+        assert_eq!(traces[1].status, 404);
+    }
+
+    #[test]
+    fn should_render_prometheus_text_test() {
+        let metrics = Metrics::default();
+        metrics
+            .start(&Request::new("a", ""), "a", "Say \"hi\"")
+            .finish(&Ok(()));
+
+        let text = metrics.render_prometheus();
+        let labels = "route=\"a\",handler=\"Say \\\"hi\\\"\"";
+        assert!(text.contains("# TYPE router_requests_received_total counter\n"));
+        assert!(text.contains(&format!("router_requests_received_total{{{labels}}} 1\n")));
+        assert!(text.contains(&format!("router_requests_failed_total{{{labels}}} 0\n")));
+        assert!(text.contains(&format!(
+            "router_request_duration_seconds_bucket{{{labels},le=\"5\"}} 1\n"
+        )));
+        assert!(text.contains(&format!(
+            "router_request_duration_seconds_count{{{labels}}} 1\n"
+        )));
+    }
+
+    #[test]
+    fn should_strip_module_path_from_type_names_test() {
+        assert_eq!(
+            short_type_name("router::handlers::FileStorageHandler"),
+            "FileStorageHandler"
+        );
+        assert_eq!(
+            short_type_name("router::async_router::BlockingHandler<router::handlers::X>"),
+            "BlockingHandler"
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::time::Instant;
+
+use crate::{handlers::RouteHandler, request::Request};
+
+/// İsteği handler'a ulaşmadan önce ve sonra işleyen ara katman.
+///
+/// Zincirin devam etmesi için `next.run` çağrılmalıdır. Çağrılmazsa istek kısa devre
+/// yapılmış olur ve handler çalıştırılmaz.
+pub trait Middleware {
+    fn handle(&self, request: &Request, next: Next<'_>) -> anyhow::Result<()>;
+}
+
+/// Zincirde kalan ara katmanlar ve en sondaki handler.
+pub struct Next<'a> {
+    middlewares: &'a [Box<dyn Middleware>],
+    handler: &'a dyn RouteHandler,
+}
+
+impl<'a> Next<'a> {
+    pub fn new(middlewares: &'a [Box<dyn Middleware>], handler: &'a dyn RouteHandler) -> Self {
+        Next {
+            middlewares,
+            handler,
+        }
+    }
+    pub fn run(self, request: &Request) -> anyhow::Result<()> {
+        match self.middlewares.split_first() {
+            Some((middleware, rest)) => middleware.handle(request, Next::new(rest, self.handler)),
+            None => self.handler.handle(request),
+        }
+    }
+}
+
+/// Ara katmanların isteği reddetme sebepleri.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RejectError {
+    Unauthorized,
+    PayloadTooLarge { size: usize, limit: usize },
+}
+
+impl Display for RejectError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RejectError::Unauthorized => write!(f, "Request is not authorized"),
+            RejectError::PayloadTooLarge { size, limit } => {
+                write!(
+                    f,
+                    "Payload of {} bytes exceeds the limit of {}",
+                    size, limit
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for RejectError {}
+
+pub struct LoggingMiddleware;
+
+impl Middleware for LoggingMiddleware {
+    fn handle(&self, request: &Request, next: Next<'_>) -> anyhow::Result<()> {
+        println!("-> {} ({} bytes)", request.path, request.data.len());
+        let result = next.run(request);
+        match &result {
+            Ok(_) => println!("<- {} ok", request.path),
+            Err(e) => println!("<- {} failed: {}", request.path, e),
+        }
+        result
+    }
+}
+
+/// Başlıktaki anahtarı izin verilen anahtarlar ile karşılaştırır.
+pub struct AuthMiddleware {
+    pub header: String,
+    pub tokens: HashSet<String>,
+}
+
+impl AuthMiddleware {
+    pub fn new(header: &str, tokens: &[&str]) -> Self {
+        AuthMiddleware {
+            header: header.to_string(),
+            tokens: tokens.iter().map(|token| token.to_string()).collect(),
+        }
+    }
+}
+
+impl Middleware for AuthMiddleware {
+    fn handle(&self, request: &Request, next: Next<'_>) -> anyhow::Result<()> {
+        match request.header(&self.header) {
+            Some(token) if self.tokens.contains(token) => next.run(request),
+            _ => Err(RejectError::Unauthorized.into()),
+        }
+    }
+}
+
+pub struct SizeLimitMiddleware {
+    pub max_bytes: usize,
+}
+
+impl Middleware for SizeLimitMiddleware {
+    fn handle(&self, request: &Request, next: Next<'_>) -> anyhow::Result<()> {
+        if request.data.len() > self.max_bytes {
+            return Err(RejectError::PayloadTooLarge {
+                size: request.data.len(),
+                limit: self.max_bytes,
+            }
+            .into());
+        }
+        next.run(request)
+    }
+}
+
+pub struct TimingMiddleware;
+
+impl Middleware for TimingMiddleware {
+    fn handle(&self, request: &Request, next: Next<'_>) -> anyhow::Result<()> {
+        let started = Instant::now();
+        let result = next.run(request);
+        println!("{} handled in {:?}", request.path, started.elapsed());
+        result
+    }
+}
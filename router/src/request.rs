@@ -1,4 +1,33 @@
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Request {
     pub path: String,
     pub data: Vec<u8>,
+    /// İsimleri küçük harfe çevrilmiş başlık bilgileri
+    pub headers: BTreeMap<String, String>,
+    /// Eşleşen rotadaki parametrelerin değerleri. Router tarafından doldurulur.
+    pub params: BTreeMap<String, String>,
+}
+
+impl Request {
+    pub fn new(path: impl Into<String>, data: impl Into<Vec<u8>>) -> Self {
+        Request {
+            path: path.into(),
+            data: data.into(),
+            ..Default::default()
+        }
+    }
+    pub fn with_header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.insert(name.to_ascii_lowercase(), value.into());
+        self
+    }
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(String::as_str)
+    }
 }
@@ -1,25 +1,399 @@
-use crate::{handlers::RouteHandler, request::Request};
+use std::{
+    fmt::{Display, Formatter},
+    sync::Arc,
+};
 
+use crate::{
+    async_router::AsyncError,
+    composite::CompositeError,
+    handlers::{RemoteError, RouteHandler},
+    metrics::{FALLBACK_ROUTE, Metrics, UNMATCHED},
+    middleware::{Middleware, Next, RejectError},
+    request::Request,
+    routes::{PatternError, RoutePattern},
+};
+
+struct Route {
+    pattern: RoutePattern,
+    handler: Box<dyn RouteHandler>,
+}
+
+/// Bir isteğin işlenme sonucu.
+#[derive(Debug)]
+pub struct Delivery {
+    pub path: String,
+    pub result: anyhow::Result<()>,
+}
+
+/// İşlenemeyen ve tekrar denenmek üzere bekletilen istek.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeadLetter {
+    pub request: Request,
+    /// Son denemedeki hata mesajı
+    pub error: String,
+    pub attempts: u32,
+}
+
+/// İstekleri yollarına göre handler'lara yönlendirir.
+///
+/// Rotalar eklendikleri sırada denenir ve ilk eşleşen rota kullanılır. Hiçbir rota
+/// eşleşmezse istek varsayılan handler'a gönderilir. Ara katmanlar tüm rotalar için
+/// eklendikleri sırada, ilk eklenen en dışta olacak şekilde çalıştırılır.
+///
+/// Her istek için rota ve handler bazında sayaç, gecikme ve iz kaydı `metrics` ile tutulur.
+#[derive(Default)]
 pub struct Router {
     requests: Vec<Request>,
-    pub handler: Box<dyn RouteHandler>,
+    dead_letters: Vec<DeadLetter>,
+    routes: Vec<Route>,
+    middlewares: Vec<Box<dyn Middleware>>,
+    metrics: Arc<Metrics>,
+    pub handler: Option<Box<dyn RouteHandler>>,
 }
 
 impl Router {
+    /// Rotası olmayan isteklerin gönderileceği varsayılan handler ile oluşturur.
     pub fn new(handler: Box<dyn RouteHandler>) -> Self {
         Router {
-            requests: Vec::new(),
-            handler,
+            handler: Some(handler),
+            ..Default::default()
         }
     }
     pub fn add(&mut self, request: Request) {
         self.requests.push(request);
     }
+    pub fn route(
+        &mut self,
+        pattern: &str,
+        handler: Box<dyn RouteHandler>,
+    ) -> Result<(), PatternError> {
+        self.routes.push(Route {
+            pattern: RoutePattern::parse(pattern)?,
+            handler,
+        });
+        Ok(())
+    }
+    pub fn add_middleware(&mut self, middleware: Box<dyn Middleware>) {
+        self.middlewares.push(middleware);
+    }
+    pub fn metrics(&self) -> &Arc<Metrics> {
+        &self.metrics
+    }
+    /// Metriklerin başka bir router ile ortak tutulması için kullanılır
+    pub fn set_metrics(&mut self, metrics: Arc<Metrics>) {
+        self.metrics = metrics;
+    }
 
-    pub fn apply(&self) -> anyhow::Result<()> {
-        for r in self.requests.iter() {
-            self.handler.handle(r)?;
+    /// İsteği eşleşen handler'a ara katmanlardan geçirerek iletir.
+    pub fn dispatch(&self, request: &Request) -> anyhow::Result<()> {
+        let matched = self.routes.iter().find_map(|route| {
+            route
+                .pattern
+                .matches(&request.path)
+                .map(|params| (route, params))
+        });
+        match matched {
+            Some((route, params)) => {
+                let mut request = request.clone();
+                request.params = params;
+                let handler = route.handler.as_ref();
+                let span = self
+                    .metrics
+                    .start(&request, route.pattern.as_str(), handler.name());
+                let result = Next::new(&self.middlewares, handler).run(&request);
+                span.finish(&result);
+                result
+            }
+            None => match &self.handler {
+                Some(handler) => {
+                    let span = self.metrics.start(request, FALLBACK_ROUTE, handler.name());
+                    let result = Next::new(&self.middlewares, handler.as_ref()).run(request);
+                    span.finish(&result);
+                    result
+                }
+                None => {
+                    let span = self.metrics.start(request, UNMATCHED, UNMATCHED);
+                    let result = Err(RouteError::NotFound(request.path.clone()).into());
+                    span.finish(&result);
+                    result
+                }
+            },
         }
-        Ok(())
+    }
+
+    /// İşlenmeyi bekleyen istekler
+    pub fn pending(&self) -> &[Request] {
+        &self.requests
+    }
+    pub fn dead_letters(&self) -> &[DeadLetter] {
+        &self.dead_letters
+    }
+
+    /// Bekleyen tüm istekleri kuyruktan alarak işler ve her biri için sonucu döner.
+    ///
+    /// Başarıyla işlenen istekler kuyruktan silinir. Hata alan istekler diğerlerinin
+    /// işlenmesini engellemez, hata mesajı ile birlikte dead-letter kuyruğuna taşınır.
+    pub fn apply(&mut self) -> Vec<Delivery> {
+        let requests = std::mem::take(&mut self.requests);
+        requests
+            .into_iter()
+            .map(|request| self.deliver(request, 1))
+            .collect()
+    }
+
+    /// Dead-letter kuyruğundaki istekleri tekrar dener. Yine başarısız olanlar deneme
+    /// sayıları artırılarak kuyrukta kalır.
+    pub fn retry_dead_letters(&mut self) -> Vec<Delivery> {
+        let letters = std::mem::take(&mut self.dead_letters);
+        letters
+            .into_iter()
+            .map(|letter| self.deliver(letter.request, letter.attempts + 1))
+            .collect()
+    }
+
+    fn deliver(&mut self, request: Request, attempts: u32) -> Delivery {
+        let result = self.dispatch(&request);
+        let path = request.path.clone();
+        if let Err(e) = &result {
+            self.dead_letters.push(DeadLetter {
+                request,
+                error: format!("{:#}", e),
+                attempts,
+            });
+        }
+        Delivery { path, result }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RouteError {
+    NotFound(String),
+}
+
+impl Display for RouteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RouteError::NotFound(path) => write!(f, "No route found for '{}'", path),
+        }
+    }
+}
+
+impl std::error::Error for RouteError {}
+
+/// Handler sonucunu HTTP durum koduna çevirir.
+pub fn status_for(result: &anyhow::Result<()>) -> u16 {
+    let Err(error) = result else {
+        return 200;
+    };
+    if let Some(RouteError::NotFound(_)) = error.downcast_ref() {
+        return 404;
+    }
+    if let Some(composite) = error.downcast_ref::<CompositeError>() {
+        return match composite {
+            CompositeError::NoMatchingBranch(_) => 404,
+            CompositeError::FanOutFailed { .. } => 502,
+        };
+    }
+    if let Some(rejection) = error.downcast_ref::<RejectError>() {
+        return match rejection {
+            RejectError::Unauthorized => 401,
+            RejectError::PayloadTooLarge { .. } => 413,
+        };
+    }
+    if let Some(remote) = error.downcast_ref::<RemoteError>() {
+        return match remote {
+            RemoteError::Status { .. } => 502,
+            RemoteError::Transport { .. } => 504,
+        };
+    }
+    if let Some(error) = error.downcast_ref::<AsyncError>() {
+        return match error {
+            AsyncError::QueueFull | AsyncError::Closed => 503,
+            AsyncError::Timeout { .. } => 504,
+        };
+    }
+    500
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::{AuthMiddleware, RejectError, SizeLimitMiddleware};
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    type Calls = Rc<RefCell<Vec<String>>>;
+
+    struct Recorder {
+        name: &'static str,
+        calls: Calls,
+    }
+
+    impl RouteHandler for Recorder {
+        fn handle(&self, request: &Request) -> anyhow::Result<()> {
+            let id = request.param("id").unwrap_or("-");
+            self.calls
+                .borrow_mut()
+                .push(format!("{} {} {}", self.name, request.path, id));
+            Ok(())
+        }
+    }
+
+    struct Trace {
+        name: &'static str,
+        calls: Calls,
+    }
+
+    impl Middleware for Trace {
+        fn handle(&self, request: &Request, next: Next<'_>) -> anyhow::Result<()> {
+            self.calls
+                .borrow_mut()
+                .push(format!("{} before", self.name));
+            let result = next.run(request);
+            self.calls.borrow_mut().push(format!("{} after", self.name));
+            result
+        }
+    }
+
+    // İlk `failures` isteği reddeder
+    struct Flaky {
+        failures: Cell<u32>,
+        calls: Calls,
+    }
+
+    impl RouteHandler for Flaky {
+        fn handle(&self, request: &Request) -> anyhow::Result<()> {
+            if self.failures.get() > 0 {
+                self.failures.set(self.failures.get() - 1);
+                anyhow::bail!("{} is unavailable", request.path);
+            }
+            self.calls.borrow_mut().push(request.path.clone());
+            Ok(())
+        }
+    }
+
+    fn recorder(name: &'static str, calls: &Calls) -> Box<Recorder> {
+        Box::new(Recorder {
+            name,
+            calls: Rc::clone(calls),
+        })
+    }
+
+    #[test]
+    fn should_route_requests_by_path_test() {
+        let calls = Calls::default();
+        let mut router = Router::new(recorder("fallback", &calls));
+        router
+            .route("api/users/{id}", recorder("users", &calls))
+            .unwrap();
+        router.route("api/**", recorder("api", &calls)).unwrap();
+
+        router.dispatch(&Request::new("api/users/7", "")).unwrap();
+        router.dispatch(&Request::new("api/products", "")).unwrap();
+        router.dispatch(&Request::new("health", "")).unwrap();
+
+        assert_eq!(
+            *calls.borrow(),
+            vec![
+                "users api/users/7 7",
+                "api api/products -",
+                "fallback health -"
+            ]
+        );
+
+        let metrics = router.metrics();
+        assert_eq!(metrics.route_counters("api/users/{id}").succeeded, 1);
+        assert_eq!(metrics.route_counters(FALLBACK_ROUTE).received, 1);
+        assert_eq!(metrics.handler_counters("Recorder").received, 3);
+        let traces = metrics.traces();
+        assert_eq!(traces[1].path, "api/products");
+        assert_eq!(traces[1].route, "api/**");
+        assert_eq!(traces[1].status, 200);
+
+        let empty = Router::default();
+        let error = empty.dispatch(&Request::new("health", "")).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<RouteError>(),
+            Some(&RouteError::NotFound("health".to_string()))
+        );
+    }
+
+    #[test]
+    fn should_run_middlewares_in_order_and_short_circuit_test() {
+        let calls = Calls::default();
+        let mut router = Router::default();
+        router.route("api/**", recorder("api", &calls)).unwrap();
+        router.add_middleware(Box::new(Trace {
+            name: "outer",
+            calls: Rc::clone(&calls),
+        }));
+        router.add_middleware(Box::new(AuthMiddleware::new("X-Api-Key", &["secret"])));
+        router.add_middleware(Box::new(SizeLimitMiddleware { max_bytes: 4 }));
+
+        let request = Request::new("api/orders", "abc").with_header("x-api-key", "secret");
+        router.dispatch(&request).unwrap();
+        assert_eq!(
+            *calls.borrow(),
+            vec!["outer before", "api api/orders -", "outer after"]
+        );
+
+        calls.borrow_mut().clear();
+        let error = router
+            .dispatch(&Request::new("api/orders", "abc"))
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<RejectError>(),
+            Some(&RejectError::Unauthorized)
+        );
+        let error = router
+            .dispatch(&Request::new("api/orders", "too big").with_header("X-Api-Key", "secret"))
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<RejectError>(),
+            Some(RejectError::PayloadTooLarge { size: 7, limit: 4 })
+        ));
+        assert_eq!(
+            *calls.borrow(),
+            vec!["outer before", "outer after", "outer before", "outer after"]
+        );
+    }
+
+    #[test]
+    fn should_drain_queue_and_dead_letter_failed_requests_test() {
+        let calls = Calls::default();
+        let mut router = Router::new(recorder("fallback", &calls));
+        router
+            .route(
+                "flaky",
+                Box::new(Flaky {
+                    failures: Cell::new(2),
+                    calls: Rc::clone(&calls),
+                }),
+            )
+            .unwrap();
+        router.add(Request::new("first", ""));
+        router.add(Request::new("flaky", ""));
+        router.add(Request::new("last", ""));
+
+        let deliveries = router.apply();
+        let failed: Vec<_> = deliveries.iter().map(|d| d.result.is_err()).collect();
+        assert_eq!(failed, vec![false, true, false]);
+        assert!(router.pending().is_empty());
+        assert!(router.apply().is_empty());
+        assert_eq!(*calls.borrow(), vec!["fallback first -", "fallback last -"]);
+        assert_eq!(
+            router.dead_letters(),
+            &[DeadLetter {
+                request: Request::new("flaky", ""),
+                error: "flaky is unavailable".to_string(),
+                attempts: 1,
+            }]
+        );
+
+        assert!(router.retry_dead_letters()[0].result.is_err());
+        assert_eq!(router.dead_letters()[0].attempts, 2);
+        assert!(router.retry_dead_letters()[0].result.is_ok());
+        assert!(router.dead_letters().is_empty());
+        assert_eq!(calls.borrow().last().unwrap(), "flaky");
     }
 }
@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// Wildcard ile eşleşen yolun kalanının saklandığı parametre ismi
+pub const REST_PARAM: &str = "*";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Param(String),
+    /// Tek bir bölümle eşleşir
+    Any,
+    /// Yolun kalanı ile eşleşir, sadece son bölüm olabilir
+    Rest,
+}
+
+/// `api/users/{id}` gibi bölümlerden oluşan yol kalıbı.
+///
+/// - `users` gibi bölümler birebir eşleşir
+/// - `{id}` herhangi bir bölümle eşleşir ve değeri `id` parametresine yazılır
+/// - `*` herhangi bir bölümle eşleşir
+/// - `**` sadece sonda kullanılabilir ve yolun kalanı ile eşleşir (boş da olabilir)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutePattern {
+    pattern: String,
+    segments: Vec<Segment>,
+}
+
+impl RoutePattern {
+    pub fn parse(pattern: &str) -> Result<Self, PatternError> {
+        let parts = split(pattern);
+        let mut segments = Vec::with_capacity(parts.len());
+        for (index, part) in parts.iter().enumerate() {
+            let segment = match *part {
+                "**" if index + 1 == parts.len() => Segment::Rest,
+                "**" => return Err(PatternError::RestNotLast(pattern.to_string())),
+                "*" => Segment::Any,
+                _ if part.starts_with('{') && part.ends_with('}') => {
+                    let name = &part[1..part.len() - 1];
+                    if name.is_empty() || name.contains(['{', '}']) {
+                        return Err(PatternError::InvalidParam(part.to_string()));
+                    }
+                    Segment::Param(name.to_string())
+                }
+                _ if part.contains(['{', '}', '*']) => {
+                    return Err(PatternError::InvalidParam(part.to_string()));
+                }
+                _ => Segment::Literal(part.to_string()),
+            };
+            segments.push(segment);
+        }
+        Ok(RoutePattern {
+            pattern: pattern.to_string(),
+            segments,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Yol kalıba uyuyorsa yakalanan parametreleri döner.
+    pub fn matches(&self, path: &str) -> Option<BTreeMap<String, String>> {
+        let parts = split(path);
+        let mut params = BTreeMap::new();
+        for (index, segment) in self.segments.iter().enumerate() {
+            if *segment == Segment::Rest {
+                params.insert(
+                    REST_PARAM.to_string(),
+                    parts[index.min(parts.len())..].join("/"),
+                );
+                return Some(params);
+            }
+            let part = parts.get(index)?;
+            match segment {
+                Segment::Literal(literal) if literal != part => return None,
+                Segment::Param(name) => {
+                    params.insert(name.clone(), part.to_string());
+                }
+                _ => {}
+            }
+        }
+        (parts.len() == self.segments.len()).then_some(params)
+    }
+}
+
+// Baştaki, sondaki ve tekrarlanan '/' karakterleri göz ardı edilir
+pub(crate) fn split(path: &str) -> Vec<&str> {
+    path.split('/').filter(|part| !part.is_empty()).collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatternError {
+    RestNotLast(String),
+    InvalidParam(String),
+}
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatternError::RestNotLast(pattern) => {
+                write!(f, "'**' must be the last segment in '{}'", pattern)
+            }
+            PatternError::InvalidParam(segment) => write!(f, "Invalid segment '{}'", segment),
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(pairs: &[(&str, &str)]) -> Option<BTreeMap<String, String>> {
+        Some(
+            pairs
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn should_match_literals_params_and_wildcards_test() {
+        let users = RoutePattern::parse("api/users/{id}").unwrap();
+        assert_eq!(users.matches("/api/users/42"), params(&[("id", "42")]));
+        assert_eq!(users.matches("api/users"), None);
+        assert_eq!(users.matches("api/users/42/orders"), None);
+        assert_eq!(users.matches("api/products/42"), None);
+
+        let any = RoutePattern::parse("api/*/title").unwrap();
+        assert_eq!(any.matches("api/users/title"), params(&[]));
+        assert_eq!(any.matches("api/users/name"), None);
+
+        let rest = RoutePattern::parse("static/**").unwrap();
+        assert_eq!(
+            rest.matches("static/css/site.css"),
+            params(&[("*", "css/site.css")])
+        );
+        assert_eq!(rest.matches("static"), params(&[("*", "")]));
+    }
+
+    #[test]
+    fn should_reject_invalid_patterns_test() {
+        assert_eq!(
+            RoutePattern::parse("**/users"),
+            Err(PatternError::RestNotLast("**/users".to_string()))
+        );
+        assert!(RoutePattern::parse("api/{}").is_err());
+        assert!(RoutePattern::parse("api/user{id}").is_err());
+    }
+}
@@ -0,0 +1,400 @@
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    config::ConfigWatcher,
+    request::Request,
+    router::{Router, status_for},
+};
+
+/// Yerel bir porttan gelen POST isteklerini router'a ileten HTTP/1.1 sunucusu.
+///
+/// `metrics_path` yoluna gelen GET istekleri router metriklerini Prometheus metin
+/// formatında döner. Handler'lar thread'ler arasında paylaşılamadığı için bağlantılar sırayla
+/// işlenir. Her bağlantıda tek bir istek okunur ve cevaptan sonra bağlantı kapatılır.
+///
+/// Yapılandırma dosyası verilmişse her bağlantıdan önce dosyanın değişip değişmediğine
+/// bakılır. Router sadece istekler arasında değiştirildiği için işlenmekte olan istekler
+/// etkilenmez. Hatalı bir değişiklikte eski router ile devam edilir.
+pub struct HttpServer {
+    listener: TcpListener,
+    router: Router,
+    config: Option<ConfigWatcher>,
+    pub max_body: usize,
+    /// Bir isteğin tamamının okunması için verilen süre. Yavaş veya boşta bekleyen bir
+    /// istemci diğer bağlantıları bundan uzun bekletemez.
+    pub read_timeout: Duration,
+    pub metrics_path: String,
+}
+
+enum Incoming {
+    Post(Request),
+    Metrics,
+}
+
+impl HttpServer {
+    pub fn bind(address: impl ToSocketAddrs, router: Router) -> io::Result<Self> {
+        Ok(HttpServer {
+            listener: TcpListener::bind(address)?,
+            router,
+            config: None,
+            max_body: 1024 * 1024,
+            read_timeout: Duration::from_secs(5),
+            metrics_path: "metrics".to_string(),
+        })
+    }
+    /// Router'ı izlenen yapılandırma dosyasından oluşturarak sunucuyu başlatır.
+    pub fn bind_with_config(
+        address: impl ToSocketAddrs,
+        config: impl Into<PathBuf>,
+    ) -> anyhow::Result<Self> {
+        let (watcher, router) = ConfigWatcher::open(config)?;
+        let mut server = Self::bind(address, router)?;
+        server.config = Some(watcher);
+        Ok(server)
+    }
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+    pub fn router(&self) -> &Router {
+        &self.router
+    }
+
+    /// Bağlantıları sonsuza kadar kabul eder. Tek bir bağlantıdaki hata sunucuyu durdurmaz.
+    pub fn serve(&mut self) -> io::Result<()> {
+        loop {
+            if let Err(e) = self.handle_next() {
+                eprintln!("Connection failed. {}", e);
+            }
+        }
+    }
+
+    /// Sıradaki bağlantıyı kabul eder, isteği işler ve cevabı yazar.
+    pub fn handle_next(&mut self) -> io::Result<()> {
+        let (stream, _) = self.listener.accept()?;
+        stream.set_write_timeout(Some(self.read_timeout))?;
+        self.reload();
+        let mut reader = BufReader::new(DeadlineStream {
+            stream,
+            deadline: Instant::now() + self.read_timeout,
+        });
+        let status = match self.read_request(&mut reader) {
+            Ok(Incoming::Post(request)) => status_for(&self.router.dispatch(&request)),
+            Ok(Incoming::Metrics) => {
+                let body = self.router.metrics().render_prometheus();
+                return write_response(
+                    &mut reader.get_mut().stream,
+                    200,
+                    PROMETHEUS_CONTENT_TYPE,
+                    &body,
+                );
+            }
+            Err(status) => {
+                // İstek tamamen okunmamış olabilir. Cevap yazıldıktan sonra kalan veri süre
+                // dolana kadar atılır ve bağlantı kapatılır
+                let stream = &mut reader.get_mut().stream;
+                write_response(stream, status, "text/plain", reason(status))?;
+                stream.shutdown(Shutdown::Write)?;
+                let _ = io::copy(&mut reader.take(MAX_DISCARD), &mut io::sink());
+                return Ok(());
+            }
+        };
+        write_response(
+            &mut reader.get_mut().stream,
+            status,
+            "text/plain",
+            reason(status),
+        )
+    }
+
+    fn reload(&mut self) {
+        let Some(result) = self.config.as_mut().and_then(ConfigWatcher::poll) else {
+            return;
+        };
+        match result {
+            Ok(mut router) => {
+                // Metrikler yeniden yüklemeler arasında korunur
+                router.set_metrics(Arc::clone(self.router.metrics()));
+                self.router = router;
+                println!("Configuration reloaded");
+            }
+            Err(e) => eprintln!("Configuration is not reloaded. {}", e),
+        }
+    }
+
+    fn read_request(&self, reader: &mut BufReader<DeadlineStream>) -> Result<Incoming, u16> {
+        // İstek satırı ve başlıklar birlikte `MAX_HEADER_BYTES` ile sınırlanır
+        let mut head = reader.by_ref().take(MAX_HEADER_BYTES);
+        let request_line = read_head_line(&mut head)?;
+        let mut parts = request_line.split_whitespace();
+        let (Some(method), Some(target), Some(_version)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(400);
+        };
+
+        let mut request = Request::default();
+        for count in 0.. {
+            let line = read_head_line(&mut head)?;
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if count == MAX_HEADER_LINES {
+                return Err(431);
+            }
+            let (name, value) = line.split_once(':').ok_or(400u16)?;
+            request = request.with_header(name.trim(), value.trim());
+        }
+        // Sorgu parametreleri yönlendirmede kullanılmaz
+        let path = target.split('?').next().unwrap_or_default();
+        let path = path.trim_start_matches('/');
+        if method == "GET" && path == self.metrics_path {
+            return Ok(Incoming::Metrics);
+        }
+        if method != "POST" {
+            return Err(405);
+        }
+
+        let length = match request.header("content-length") {
+            Some(value) => value.parse::<usize>().map_err(|_| 400u16)?,
+            None => return Err(411),
+        };
+        if length > self.max_body {
+            return Err(413);
+        }
+        request.data = vec![0; length];
+        reader.read_exact(&mut request.data).map_err(|_| 400u16)?;
+        request.path = path.to_string();
+        Ok(Incoming::Post(request))
+    }
+}
+
+/// İstek satırı ve başlıkların toplam büyüklüğü
+const MAX_HEADER_BYTES: u64 = 16 * 1024;
+/// Bir istekte bulunabilecek en fazla başlık sayısı
+const MAX_HEADER_LINES: usize = 100;
+
+// Sınıra ulaşılmışsa 431, bağlantı satırın ortasında kapanmışsa 400 döner
+fn read_head_line<R: BufRead>(head: &mut io::Take<R>) -> Result<String, u16> {
+    let mut line = String::new();
+    head.read_line(&mut line).map_err(|_| 400u16)?;
+    if line.ends_with('\n') {
+        Ok(line)
+    } else if head.limit() == 0 {
+        Err(431)
+    } else {
+        Err(400)
+    }
+}
+
+/// Hatalı bir isteğin ardından okunup atılacak en fazla veri
+const MAX_DISCARD: u64 = 64 * 1024;
+
+/// Okuma süresini her okumada bağlantının kalan süresine göre ayarlar. Böylece veriyi
+/// parça parça gönderen bir istemci de verilen süreyi aşamaz.
+struct DeadlineStream {
+    stream: TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineStream {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buffer)
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Internal Server Error",
+    }
+}
+
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+fn write_response(
+    stream: &mut TcpStream,
+    status: u16,
+    content_type: &str,
+    body: &str,
+) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason(status),
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::RouteHandler;
+    use crate::middleware::SizeLimitMiddleware;
+    use std::sync::mpsc::{Sender, channel};
+    use std::thread;
+
+    struct Forward {
+        sender: Sender<(String, Vec<u8>)>,
+    }
+
+    impl RouteHandler for Forward {
+        fn handle(&self, request: &Request) -> anyhow::Result<()> {
+            self.sender
+                .send((request.path.clone(), request.data.clone()))?;
+            Ok(())
+        }
+    }
+
+    struct Broken;
+
+    impl RouteHandler for Broken {
+        fn handle(&self, _request: &Request) -> anyhow::Result<()> {
+            anyhow::bail!("disk is full")
+        }
+    }
+
+    fn status(result: Result<ureq::Response, ureq::Error>) -> u16 {
+        match result {
+            Ok(response) => response.status(),
+            Err(ureq::Error::Status(status, _)) => status,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn should_dispatch_posts_and_return_status_per_result_test() {
+        let (sender, received) = channel();
+        let (address_sender, address) = channel();
+        thread::spawn(move || {
+            let mut router = Router::default();
+            router
+                .route("api/users/{id}", Box::new(Forward { sender }))
+                .unwrap();
+            router.route("broken", Box::new(Broken)).unwrap();
+            router.add_middleware(Box::new(SizeLimitMiddleware { max_bytes: 8 }));
+            let mut server = HttpServer::bind("127.0.0.1:0", router).unwrap();
+            address_sender.send(server.local_addr().unwrap()).unwrap();
+            for _ in 0..6 {
+                server.handle_next().unwrap();
+            }
+        });
+        let base = format!("http://{}", address.recv().unwrap());
+
+        let post = |path: &str, body: &[u8]| {
+            status(ureq::post(&format!("{base}/{path}")).send_bytes(body))
+        };
+        assert_eq!(post("api/users/7?debug=1", b"{\"a\":1}"), 200);
+        assert_eq!(
+            received.recv().unwrap(),
+            ("api/users/7".to_string(), b"{\"a\":1}".to_vec())
+        );
+        assert_eq!(post("api/orders", b""), 404);
+        assert_eq!(post("api/users/7", b"far too long"), 413);
+        assert_eq!(post("broken", b""), 500);
+        assert_eq!(
+            status(ureq::get(&format!("{base}/api/users/7")).call()),
+            405
+        );
+
+        let metrics = ureq::get(&format!("{base}/metrics"))
+            .call()
+            .unwrap()
+            .into_string()
+            .unwrap();
+        let labels = "route=\"api/users/{id}\",handler=\"Forward\"";
+        assert!(metrics.contains(&format!("router_requests_received_total{{{labels}}} 2\n")));
+        assert!(metrics.contains(&format!("router_requests_failed_total{{{labels}}} 1\n")));
+        assert!(
+            metrics
+                .contains("router_requests_failed_total{route=\"<none>\",handler=\"<none>\"} 1\n")
+        );
+    }
+
+    #[test]
+    fn should_not_let_slow_clients_block_others_test() {
+        let (address_sender, address) = channel();
+        thread::spawn(move || {
+            let mut server = HttpServer::bind("127.0.0.1:0", Router::default()).unwrap();
+            server.read_timeout = Duration::from_millis(200);
+            address_sender.send(server.local_addr().unwrap()).unwrap();
+            for _ in 0..3 {
+                let _ = server.handle_next();
+            }
+        });
+        let address = address.recv().unwrap();
+
+        // Başlığın ortasında bekleyen istemci
+        let mut idle = TcpStream::connect(address).unwrap();
+        idle.write_all(b"POST /a HTTP/1.1\r\n").unwrap();
+        // Gövdesi büyük olduğu için reddedilen ama gövdeyi göndermeye devam eden istemci
+        let mut large = TcpStream::connect(address).unwrap();
+        large
+            .write_all(b"POST /a HTTP/1.1\r\nContent-Length: 99999999\r\n\r\npartial")
+            .unwrap();
+
+        let started = Instant::now();
+        let mut response = String::new();
+        large.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 413 "));
+
+        let base = format!("http://{}", address);
+        assert_eq!(status(ureq::get(&format!("{base}/metrics")).call()), 200);
+        assert!(started.elapsed() < Duration::from_secs(2));
+        drop(idle);
+    }
+
+    #[test]
+    fn should_reject_oversized_headers_test() {
+        let (address_sender, address) = channel();
+        thread::spawn(move || {
+            let mut server = HttpServer::bind("127.0.0.1:0", Router::default()).unwrap();
+            address_sender.send(server.local_addr().unwrap()).unwrap();
+            for _ in 0..2 {
+                let _ = server.handle_next();
+            }
+        });
+        let address = address.recv().unwrap();
+        let send = |head: String| {
+            let mut stream = TcpStream::connect(address).unwrap();
+            // Sunucu cevabı yazıp bağlantıyı kapatabileceği için yazma hatası göz ardı edilir
+            let _ = stream.write_all(head.as_bytes());
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+
+        let long_header = format!(
+            "POST /a HTTP/1.1\r\nX-Long: {}\r\n\r\n",
+            "a".repeat(32 * 1024)
+        );
+        assert!(send(long_header).starts_with("HTTP/1.1 431 "));
+        let many_headers = format!("POST /a HTTP/1.1\r\n{}\r\n", "X-A: 1\r\n".repeat(200));
+        assert!(send(many_headers).starts_with("HTTP/1.1 431 "));
+    }
+}
@@ -0,0 +1,325 @@
+use std::{
+    fmt::{Display, Formatter},
+    fs::File,
+    io::{self, BufReader, Read},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::request::Request;
+
+/// Her kaydın başında yer alan işaret
+pub const MAGIC: &[u8; 4] = b"RREC";
+
+/// Bir kaydın zaman ile veri arasındaki bölümünün alabileceği en büyük uzunluk. Okurken
+/// bundan uzun görünen kayıtlar bozuk kabul edilir.
+pub const MAX_RECORD_SIZE: usize = 64 * 1024 * 1024;
+
+/// Depolama dosyasındaki tek bir kayıt.
+///
+/// Dosyadaki formatı (tüm sayılar little-endian):
+///
+/// ```text
+/// "RREC" | uzunluk: u32 | zaman: u64 | yol uzunluğu: u16 | yol
+///        | içerik tipi uzunluğu: u16 | içerik tipi | veri | crc32: u32
+/// ```
+///
+/// `uzunluk` zaman ile veri arasındaki bölümün uzunluğudur, checksum bu bölüm üzerinden
+/// hesaplanır.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredRecord {
+    pub path: String,
+    /// Unix zamanı, milisaniye
+    pub timestamp: u64,
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+impl StoredRecord {
+    pub fn from_request(request: &Request) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or_default();
+        StoredRecord {
+            path: request.path.clone(),
+            timestamp,
+            content_type: request
+                .header("content-type")
+                .unwrap_or("application/octet-stream")
+                .to_string(),
+            data: request.data.clone(),
+        }
+    }
+
+    /// Kaydı dosyadaki formatına çevirir. Alanlar formata sığmıyorsa `TooLarge` döner.
+    pub fn encode(&self) -> Result<Vec<u8>, StorageError> {
+        let mut body =
+            Vec::with_capacity(12 + self.path.len() + self.content_type.len() + self.data.len());
+        body.extend_from_slice(&self.timestamp.to_le_bytes());
+        write_text(&mut body, "path", &self.path)?;
+        write_text(&mut body, "content type", &self.content_type)?;
+        body.extend_from_slice(&self.data);
+        if body.len() > MAX_RECORD_SIZE {
+            return Err(StorageError::TooLarge {
+                field: "record",
+                size: body.len(),
+                limit: MAX_RECORD_SIZE,
+            });
+        }
+
+        let mut frame = Vec::with_capacity(body.len() + 12);
+        frame.extend_from_slice(MAGIC);
+        frame.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        frame.extend_from_slice(&body);
+        frame.extend_from_slice(&crc32(&body).to_le_bytes());
+        Ok(frame)
+    }
+
+    fn decode(body: &[u8]) -> Option<Self> {
+        let timestamp = u64::from_le_bytes(body.get(..8)?.try_into().ok()?);
+        let (path, rest) = read_text(&body[8..])?;
+        let (content_type, data) = read_text(rest)?;
+        Some(StoredRecord {
+            path,
+            timestamp,
+            content_type,
+            data: data.to_vec(),
+        })
+    }
+}
+
+fn write_text(buffer: &mut Vec<u8>, field: &'static str, text: &str) -> Result<(), StorageError> {
+    let length = u16::try_from(text.len()).map_err(|_| StorageError::TooLarge {
+        field,
+        size: text.len(),
+        limit: u16::MAX as usize,
+    })?;
+    buffer.extend_from_slice(&length.to_le_bytes());
+    buffer.extend_from_slice(text.as_bytes());
+    Ok(())
+}
+
+fn read_text(buffer: &[u8]) -> Option<(String, &[u8])> {
+    let length = u16::from_le_bytes(buffer.get(..2)?.try_into().ok()?) as usize;
+    let text = buffer.get(2..2 + length)?;
+    Some((
+        String::from_utf8_lossy(text).to_string(),
+        &buffer[2 + length..],
+    ))
+}
+
+/// IEEE CRC-32
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Depolama dosyasındaki kayıtları sırayla okur.
+///
+/// Dosya sonundaki yarım kalmış kayıt `Truncated`, bozulmuş kayıt `Corrupt` hatası olarak
+/// döner. Hatadan sonra okuma durur.
+pub struct RecordReader<R> {
+    reader: R,
+    offset: u64,
+    is_done: bool,
+}
+
+impl RecordReader<BufReader<File>> {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: Read> RecordReader<R> {
+    pub fn new(reader: R) -> Self {
+        RecordReader {
+            reader,
+            offset: 0,
+            is_done: false,
+        }
+    }
+
+    fn read_record(&mut self) -> Result<Option<StoredRecord>, StorageError> {
+        let start = self.offset;
+        let mut header = [0u8; 8];
+        let read = read_full(&mut self.reader, &mut header)?;
+        if read == 0 {
+            return Ok(None);
+        }
+        if read < header.len() {
+            return Err(StorageError::Truncated { offset: start });
+        }
+        if &header[..4] != MAGIC {
+            return Err(StorageError::Corrupt { offset: start });
+        }
+        let length = u32::from_le_bytes(header[4..].try_into().unwrap()) as usize;
+        // Uzunluk doğrulanmadan yer ayrılmaz, bozuk bir başlık büyük bir ayırmaya yol açmasın
+        if length > MAX_RECORD_SIZE {
+            return Err(StorageError::Corrupt { offset: start });
+        }
+        let mut body = vec![0u8; length + 4];
+        if read_full(&mut self.reader, &mut body)? < body.len() {
+            return Err(StorageError::Truncated { offset: start });
+        }
+        let checksum = u32::from_le_bytes(body[length..].try_into().unwrap());
+        body.truncate(length);
+        if crc32(&body) != checksum {
+            return Err(StorageError::Corrupt { offset: start });
+        }
+        self.offset += (header.len() + length + 4) as u64;
+        StoredRecord::decode(&body)
+            .map(Some)
+            .ok_or(StorageError::Corrupt { offset: start })
+    }
+}
+
+impl<R: Read> Iterator for RecordReader<R> {
+    type Item = Result<StoredRecord, StorageError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done {
+            return None;
+        }
+        let result = self.read_record().transpose();
+        if !matches!(result, Some(Ok(_))) {
+            self.is_done = true;
+        }
+        result
+    }
+}
+
+// Dosya sonuna gelinene kadar tamponu doldurur ve okunan byte sayısını döner
+fn read_full<R: Read>(reader: &mut R, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut total = 0;
+    while total < buffer.len() {
+        match reader.read(&mut buffer[total..]) {
+            Ok(0) => break,
+            Ok(count) => total += count,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(total)
+}
+
+#[derive(Debug)]
+pub enum StorageError {
+    Io(io::Error),
+    /// Dosya verilen konumda başlayan kaydın ortasında bitiyor
+    Truncated {
+        offset: u64,
+    },
+    /// Verilen konumdaki kayıt geçerli değil veya checksum tutmuyor
+    Corrupt {
+        offset: u64,
+    },
+    /// Kaydın bir alanı formata sığmıyor
+    TooLarge {
+        field: &'static str,
+        size: usize,
+        limit: usize,
+    },
+}
+
+impl From<io::Error> for StorageError {
+    fn from(e: io::Error) -> Self {
+        StorageError::Io(e)
+    }
+}
+
+impl Display for StorageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageError::Io(e) => write!(f, "Storage could not be read. {}", e),
+            StorageError::Truncated { offset } => {
+                write!(f, "Record at offset {} is truncated", offset)
+            }
+            StorageError::Corrupt { offset } => write!(f, "Record at offset {} is corrupt", offset),
+            StorageError::TooLarge { field, size, limit } => write!(
+                f,
+                "Record {} is {} bytes, at most {} bytes can be stored",
+                field, size, limit
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(path: &str, data: &str) -> StoredRecord {
+        StoredRecord {
+            path: path.to_string(),
+            timestamp: 1_700_000_000_000,
+            content_type: "application/json".to_string(),
+            data: data.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn should_read_back_written_records_test() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        let records = vec![record("api/users", "{\"a\":1}"), record("api/products", "")];
+        let bytes: Vec<u8> = records
+            .iter()
+            .flat_map(|record| record.encode().unwrap())
+            .collect();
+        let read: Vec<_> = RecordReader::new(bytes.as_slice())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(read, records);
+    }
+
+    #[test]
+    fn should_detect_truncated_and_corrupt_records_test() {
+        let first = record("api/users", "{\"a\":1}").encode().unwrap();
+        let mut bytes = first.clone();
+        bytes.extend_from_slice(&record("api/orders", "{\"b\":2}").encode().unwrap());
+        bytes.truncate(bytes.len() - 3);
+
+        let mut reader = RecordReader::new(bytes.as_slice());
+        assert!(reader.next().unwrap().is_ok());
+        assert!(matches!(
+            reader.next(),
+            Some(Err(StorageError::Truncated { offset })) if offset == first.len() as u64
+        ));
+        assert!(reader.next().is_none());
+
+        let mut corrupt = first;
+        let last = corrupt.len() - 5;
+        corrupt[last] ^= 0xff;
+        assert!(matches!(
+            RecordReader::new(corrupt.as_slice()).next(),
+            Some(Err(StorageError::Corrupt { offset: 0 }))
+        ));
+    }
+
+    #[test]
+    fn should_reject_oversized_records_test() {
+        let long_path = "a".repeat(u16::MAX as usize + 1);
+        assert!(matches!(
+            record(&long_path, "").encode(),
+            Err(StorageError::TooLarge { field: "path", .. })
+        ));
+
+        // Başlıktaki uzunluk okunmadan önce kontrol edilir
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            RecordReader::new(bytes.as_slice()).next(),
+            Some(Err(StorageError::Corrupt { offset: 0 }))
+        ));
+    }
+}